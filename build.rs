@@ -0,0 +1,53 @@
+//! Resolves the static data embedded by the `bundled-data` feature, see `ddragon::offline`.
+
+use std::{env, path::PathBuf};
+
+/// Files `StaticData::bundled` embeds, all of a single version and locale.
+const BUNDLED_FILES: [&str; 7] = [
+    "champion.json",
+    "item.json",
+    "runesReforged.json",
+    "summoner.json",
+    "profileicon.json",
+    "map.json",
+    "queues.json",
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=LEAGUE_API_STATIC_DATA_DIR");
+    println!("cargo:rerun-if-env-changed=LEAGUE_API_STATIC_DATA_LOCALE");
+    println!("cargo:rustc-check-cfg=cfg(bundled_static_data)");
+    if env::var_os("CARGO_FEATURE_BUNDLED_DATA").is_none() {
+        return;
+    }
+
+    // Without data the crate still builds, e.g. on docs.rs, and `bundled` returns None
+    let Some(dir) = env::var_os("LEAGUE_API_STATIC_DATA_DIR") else {
+        println!(
+            "cargo:warning=The bundled-data feature is enabled but LEAGUE_API_STATIC_DATA_DIR is \
+             not set, StaticData::bundled will return None"
+        );
+        return;
+    };
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let dir = manifest_dir.join(dir);
+    let missing: Vec<&str> = BUNDLED_FILES
+        .into_iter()
+        .filter(|file| !dir.join(file).is_file())
+        .collect();
+    if !missing.is_empty() {
+        panic!(
+            "LEAGUE_API_STATIC_DATA_DIR is set to {}, which is missing {}. It must contain the \
+             data/{{locale}} files of an extracted Data Dragon tarball as well as queues.json",
+            dir.display(),
+            missing.join(", ")
+        );
+    }
+
+    for file in BUNDLED_FILES {
+        println!("cargo:rerun-if-changed={}", dir.join(file).display());
+    }
+    println!("cargo:rustc-env=LEAGUE_API_BUNDLED_DATA_DIR={}", dir.display());
+    println!("cargo:rustc-cfg=bundled_static_data");
+}
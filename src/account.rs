@@ -1,7 +1,12 @@
-use log::{debug, error};
+use core::fmt;
+
+use log::error;
 use serde::{Deserialize, Serialize};
 
-use crate::region::routing::RoutingRegion;
+use crate::{
+    cache::{self, Endpoint},
+    region::routing::RoutingRegion,
+};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RiotAccount {
@@ -12,6 +17,32 @@ pub struct RiotAccount {
     tag_line: Option<String>,
 }
 
+/// Games whose data is split across shards rather than platform regions.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShardedGame {
+    #[serde(rename = "val")]
+    Valorant,
+    #[serde(rename = "lor")]
+    LegendsOfRuneterra,
+}
+
+impl fmt::Display for ShardedGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Valorant => write!(f, "val"),
+            Self::LegendsOfRuneterra => write!(f, "lor"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActiveShard {
+    puuid: String,
+    game: ShardedGame,
+    #[serde(rename = "activeShard")]
+    active_shard: String,
+}
+
 impl RiotAccount {
     /// Requests Riot account information from api.
     ///
@@ -24,13 +55,9 @@ impl RiotAccount {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/riot/account/v1/accounts/by-riot-id/{}/{}?api_key={}",
-            region.to_string(),
-            game_name,
-            tag_line,
-            api_key
+            region, game_name, tag_line, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::Account, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -47,11 +74,32 @@ impl RiotAccount {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/riot/account/v1/accounts/by-puuid/{}?api_key={}",
-            region.to_string(),
-            puuid,
-            api_key
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::Account, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the account of the player who authorised the given RSO access token.
+    ///
+    /// Unlike other endpoints this authenticates with the player's token instead of an API key.
+    pub async fn by_access_token(region: &RoutingRegion, access_token: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/riot/account/v1/accounts/me",
+            region
         );
-        let resp = reqwest::get(request_url).await.unwrap();
+        let resp = reqwest::Client::new()
+            .get(request_url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .unwrap();
         let resp = resp.text().await.expect("Could not parse");
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
@@ -62,6 +110,27 @@ impl RiotAccount {
         }
     }
 
+    /// Requests the shard the player is currently active on for the given game.
+    pub async fn active_shard(
+        region: &RoutingRegion,
+        api_key: &String,
+        game: ShardedGame,
+        puuid: &String,
+    ) -> Option<ActiveShard> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/riot/account/v1/active-shards/by-game/{}/by-puuid/{}?api_key={}",
+            region, game, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::ActiveShard, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
     pub fn get_puuid(&self) -> &String {
         &self.puuid
     }
@@ -82,3 +151,18 @@ impl RiotAccount {
         }
     }
 }
+
+impl ActiveShard {
+    pub fn get_puuid(&self) -> &String {
+        &self.puuid
+    }
+
+    pub fn get_game(&self) -> ShardedGame {
+        self.game
+    }
+
+    /// Shard name, e.g. "eu" for VALORANT or "americas" for Legends of Runeterra
+    pub fn get_active_shard(&self) -> &String {
+        &self.active_shard
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use flate2::{read::GzDecoder, Compression, GzBuilder};
+use log::error;
+use sha2::{Digest, Sha256};
+
+/// Kind of body stored in a `DiskCache`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchFile {
+    Match,
+    Timeline,
+}
+
+impl MatchFile {
+    pub const ALL: [MatchFile; 2] = [MatchFile::Match, MatchFile::Timeline];
+}
+
+impl fmt::Display for MatchFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchFile::Match => write!(f, "matches"),
+            MatchFile::Timeline => write!(f, "timelines"),
+        }
+    }
+}
+
+/// Cache of raw match-v5 match and timeline JSON, which never change once a game is over.
+///
+/// Bodies are gzipped under `{root}/{matches|timelines}/{hash[..2]}/{hash}.json.gz`, where
+/// `hash` is the hex SHA-256 of the match ID. The match ID is kept as the gzip file name so
+/// entries can be listed.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    root: PathBuf,
+}
+
+/// A body stored in a `DiskCache`.
+#[derive(Debug, Clone)]
+pub struct CachedEntry {
+    pub kind: MatchFile,
+    /// Empty for a partial file whose gzip header was never written
+    pub match_id: String,
+    /// Whether this is the `.gz.partial` file of a write that was interrupted or is ongoing
+    pub partial: bool,
+    pub path: PathBuf,
+    /// Compressed size in bytes
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl DiskCache {
+    /// Uses `root` as the cache directory, which is created on the first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path(&self, kind: MatchFile, match_id: &str) -> PathBuf {
+        let hash: String = Sha256::digest(match_id.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        self.root
+            .join(kind.to_string())
+            .join(&hash[..2])
+            .join(format!("{}.json.gz", hash))
+    }
+
+    pub fn contains(&self, kind: MatchFile, match_id: &str) -> bool {
+        self.path(kind, match_id).is_file()
+    }
+
+    /// Returns the decompressed body, None if it is not cached or unreadable.
+    pub fn read(&self, kind: MatchFile, match_id: &str) -> Option<String> {
+        let path = self.path(kind, match_id);
+        let file = fs::File::open(&path).ok()?;
+        let mut body = String::new();
+        match GzDecoder::new(file).read_to_string(&mut body) {
+            Ok(_) => Some(body),
+            Err(e) => {
+                error!("{e:?}, {}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Compresses and stores a body, replacing any previous one.
+    pub fn write(&self, kind: MatchFile, match_id: &str, body: &str) -> io::Result<()> {
+        let path = self.path(kind, match_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Written next to the final path and renamed, so readers never see a partial file
+        let partial = path.with_extension("gz.partial");
+        let file = fs::File::create(&partial)?;
+        let mut encoder = GzBuilder::new()
+            .filename(match_id)
+            .write(file, Compression::default());
+        encoder.write_all(body.as_bytes())?;
+        encoder.finish()?.sync_all()?;
+        fs::rename(partial, path)
+    }
+
+    /// Removes a body, returning whether it was cached.
+    pub fn remove(&self, kind: MatchFile, match_id: &str) -> io::Result<bool> {
+        match fs::remove_file(self.path(kind, match_id)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lists every cached body, including partial files of unfinished writes, oldest first.
+    /// Files and directories the cache did not create are skipped.
+    pub fn entries(&self) -> io::Result<Vec<CachedEntry>> {
+        let mut entries = Vec::new();
+        for kind in MatchFile::ALL {
+            let directory = self.root.join(kind.to_string());
+            if !directory.is_dir() {
+                continue;
+            }
+            for prefix in fs::read_dir(directory)? {
+                let prefix = prefix?;
+                if !prefix.file_type()?.is_dir() {
+                    continue;
+                }
+                for file in fs::read_dir(prefix.path())? {
+                    let file = file?;
+                    if !file.file_type()?.is_file() {
+                        continue;
+                    }
+                    let path = file.path();
+                    let name = path.to_string_lossy();
+                    let partial = name.ends_with(".json.gz.partial");
+                    if !partial && !name.ends_with(".json.gz") {
+                        continue;
+                    }
+                    let match_id = match stored_match_id(&path) {
+                        Some(match_id) => match_id,
+                        None if partial => String::new(),
+                        None => {
+                            error!("Missing match ID, {}", path.display());
+                            continue;
+                        }
+                    };
+                    let metadata = file.metadata()?;
+                    entries.push(CachedEntry {
+                        kind,
+                        match_id,
+                        partial,
+                        path,
+                        size: metadata.len(),
+                        modified: metadata.modified()?,
+                    });
+                }
+            }
+        }
+        entries.sort_by_key(|entry| entry.modified);
+        Ok(entries)
+    }
+
+    /// Compressed size of every cached body and partial file in bytes.
+    pub fn size(&self) -> io::Result<u64> {
+        Ok(self.entries()?.iter().map(|entry| entry.size).sum())
+    }
+
+    /// Removes bodies and partial files written more than `max_age` ago, returning the removed
+    /// entries.
+    pub fn prune_older_than(&self, max_age: Duration) -> io::Result<Vec<CachedEntry>> {
+        let now = SystemTime::now();
+        let expired = self.entries()?.into_iter().filter(|entry| {
+            now.duration_since(entry.modified)
+                .is_ok_and(|age| age > max_age)
+        });
+        remove_entries(expired)
+    }
+
+    /// Removes the oldest bodies and partial files until at most `max_size` compressed bytes
+    /// remain, returning the removed entries.
+    pub fn prune_to_size(&self, max_size: u64) -> io::Result<Vec<CachedEntry>> {
+        let entries = self.entries()?;
+        let mut size: u64 = entries.iter().map(|entry| entry.size).sum();
+        let oldest = entries.into_iter().take_while(|entry| {
+            let over = size > max_size;
+            size = size.saturating_sub(entry.size);
+            over
+        });
+        remove_entries(oldest)
+    }
+}
+
+fn stored_match_id(path: &Path) -> Option<String> {
+    let decoder = GzDecoder::new(fs::File::open(path).ok()?);
+    let filename = decoder.header()?.filename()?;
+    Some(String::from_utf8_lossy(filename).into_owned())
+}
+
+fn remove_entries(entries: impl Iterator<Item = CachedEntry>) -> io::Result<Vec<CachedEntry>> {
+    let mut removed = Vec::new();
+    for entry in entries {
+        fs::remove_file(&entry.path)?;
+        removed.push(entry);
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MATCH_ID: &str = "EUW1_1234567890";
+    const BODY: &str = r#"{"metadata":{"matchId":"EUW1_1234567890"}}"#;
+
+    /// Backdates a file so entries are ordered and old enough to prune.
+    fn set_age(path: &Path, age: Duration) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(root.path());
+        assert!(!cache.contains(MatchFile::Match, MATCH_ID));
+        assert!(cache.entries().unwrap().is_empty());
+
+        cache.write(MatchFile::Match, MATCH_ID, BODY).unwrap();
+        assert!(cache.contains(MatchFile::Match, MATCH_ID));
+        assert!(!cache.contains(MatchFile::Timeline, MATCH_ID));
+        assert_eq!(
+            cache.read(MatchFile::Match, MATCH_ID).as_deref(),
+            Some(BODY)
+        );
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, MatchFile::Match);
+        assert_eq!(entries[0].match_id, MATCH_ID);
+        assert!(!entries[0].partial);
+        assert_eq!(cache.size().unwrap(), entries[0].size);
+
+        assert!(cache.remove(MatchFile::Match, MATCH_ID).unwrap());
+        assert!(!cache.remove(MatchFile::Match, MATCH_ID).unwrap());
+        assert_eq!(cache.read(MatchFile::Match, MATCH_ID), None);
+    }
+
+    #[test]
+    fn entries_skip_stray_files() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(root.path());
+        cache.write(MatchFile::Timeline, MATCH_ID, BODY).unwrap();
+        let prefix = cache.path(MatchFile::Timeline, MATCH_ID);
+        let prefix = prefix.parent().unwrap();
+        fs::write(root.path().join("timelines").join(".DS_Store"), "").unwrap();
+        fs::write(prefix.join("notes.txt"), "").unwrap();
+        fs::create_dir(prefix.join("nested.json.gz")).unwrap();
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, MatchFile::Timeline);
+    }
+
+    #[test]
+    fn partial_files_are_listed_and_pruned() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(root.path());
+        cache.write(MatchFile::Match, MATCH_ID, BODY).unwrap();
+        let partial = cache
+            .path(MatchFile::Match, "EUW1_1")
+            .with_extension("gz.partial");
+        fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        fs::write(&partial, "interrupted").unwrap();
+        set_age(&partial, Duration::from_secs(3600));
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].partial);
+        assert_eq!(entries[0].match_id, "");
+        assert_eq!(entries[0].size, "interrupted".len() as u64);
+
+        let removed = cache.prune_older_than(Duration::from_secs(60)).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(!partial.exists());
+        assert!(cache.contains(MatchFile::Match, MATCH_ID));
+    }
+
+    #[test]
+    fn prune_to_size_removes_oldest_first() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(root.path());
+        for (age, match_id) in [(30, "EUW1_1"), (20, "EUW1_2"), (10, "EUW1_3")] {
+            cache.write(MatchFile::Match, match_id, BODY).unwrap();
+            set_age(
+                &cache.path(MatchFile::Match, match_id),
+                Duration::from_secs(age),
+            );
+        }
+        let sizes: Vec<u64> = cache.entries().unwrap().iter().map(|e| e.size).collect();
+
+        let removed = cache.prune_to_size(sizes[1] + sizes[2]).unwrap();
+        let removed: Vec<&str> = removed.iter().map(|e| e.match_id.as_str()).collect();
+        assert_eq!(removed, vec!["EUW1_1"]);
+        assert!(cache.contains(MatchFile::Match, "EUW1_2"));
+        assert!(cache.prune_to_size(u64::MAX).unwrap().is_empty());
+        assert_eq!(cache.prune_to_size(0).unwrap().len(), 2);
+        assert!(cache.entries().unwrap().is_empty());
+    }
+}
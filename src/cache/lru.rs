@@ -0,0 +1,187 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::ResponseCache;
+
+/// In-memory cache evicting the least recently used response once `capacity` is reached.
+#[derive(Debug)]
+pub struct LruCache {
+    capacity: usize,
+    inner: Mutex<LruInner>,
+}
+
+#[derive(Debug, Default)]
+struct LruInner {
+    entries: HashMap<String, LruEntry>,
+    /// Last use to key, the first entry is the least recently used
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    body: String,
+    expires: Option<Instant>,
+    used: u64,
+}
+
+impl LruCache {
+    /// Creates a cache holding at most `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(LruInner::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of stored responses, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl LruInner {
+    fn remove(&mut self, key: &str) -> Option<LruEntry> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.used);
+        Some(entry)
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+impl ResponseCache for LruCache {
+    fn get(&self, key: &str) -> Option<String> {
+        let mut inner = self.inner.lock().unwrap();
+        let expired = inner
+            .entries
+            .get(key)?
+            .expires
+            .is_some_and(|expires| expires <= Instant::now());
+        if expired {
+            inner.remove(key);
+            return None;
+        }
+        let tick = inner.next_tick();
+        let entry = inner.entries.get_mut(key)?;
+        let previous = std::mem::replace(&mut entry.used, tick);
+        let body = entry.body.clone();
+        inner.order.remove(&previous);
+        inner.order.insert(tick, key.to_string());
+        Some(body)
+    }
+
+    fn insert(&self, key: String, body: String, ttl: Option<Duration>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        inner.remove(&key);
+        while inner.entries.len() >= self.capacity {
+            let Some((_, oldest)) = inner.order.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+        let used = inner.next_tick();
+        inner.order.insert(used, key.clone());
+        inner.entries.insert(
+            key,
+            LruEntry {
+                body,
+                expires: ttl.map(|ttl| Instant::now() + ttl),
+                used,
+            },
+        );
+    }
+
+    fn remove(&self, key: &str) {
+        self.inner.lock().unwrap().remove(key);
+    }
+
+    fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries.clear();
+        inner.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(cache: &LruCache, key: &str) {
+        cache.insert(key.to_string(), format!("{} body", key), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = LruCache::new(2);
+        insert(&cache, "a");
+        insert(&cache, "b");
+        insert(&cache, "c");
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.len(), 2);
+
+        // Reading "b" makes "c" the least recently used
+        assert_eq!(cache.get("b").as_deref(), Some("b body"));
+        insert(&cache, "d");
+        assert_eq!(cache.get("c"), None);
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("d").is_some());
+    }
+
+    #[test]
+    fn replacing_an_entry_evicts_nothing() {
+        let cache = LruCache::new(2);
+        insert(&cache, "a");
+        insert(&cache, "b");
+        cache.insert("a".to_string(), "new body".to_string(), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a").as_deref(), Some("new body"));
+
+        // Replacing "a" refreshed it, so "b" goes first
+        insert(&cache, "c");
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("a").is_some());
+    }
+
+    #[test]
+    fn expired_entries_are_dropped() {
+        let cache = LruCache::new(2);
+        cache.insert("a".to_string(), "body".to_string(), Some(Duration::ZERO));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("a"), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn remove_clear_and_zero_capacity() {
+        let cache = LruCache::new(3);
+        insert(&cache, "a");
+        insert(&cache, "b");
+        cache.remove("a");
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.len(), 1);
+        cache.clear();
+        assert!(cache.is_empty());
+
+        let disabled = LruCache::new(0);
+        insert(&disabled, "a");
+        assert!(disabled.is_empty());
+    }
+}
@@ -0,0 +1,474 @@
+//! Response cache shared by every API-key endpoint.
+//!
+//! Backends, TTL overrides and rate limit state are held by a `Cache` handle. Requests go
+//! through the handle of the `Cache::scope` they run in, or through `Cache::global` outside of
+//! any scope, so separate clients or tests can each use their own. The free functions of this
+//! module act on `Cache::current`.
+//!
+//! Caching is off until a backend is installed with `set_cache`. Responses are keyed by
+//! endpoint and request URL, without the API key, and only successful responses are stored.
+//! RSO endpoints, which are authenticated per user, and tournament writes are never cached.
+//!
+//! With the `disk-cache` feature, match and timeline bodies can also be persisted with
+//! `set_disk_cache`, which is checked before the in-memory cache.
+
+#[cfg(feature = "disk-cache")]
+pub mod disk;
+pub mod lru;
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, OnceLock, RwLock},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "disk-cache")]
+use log::error;
+use reqwest::StatusCode;
+
+#[cfg(feature = "disk-cache")]
+use self::disk::DiskCache;
+pub use self::lru::LruCache;
+
+/// Storage for response bodies, implemented by `LruCache` or a custom backend such as Redis.
+pub trait ResponseCache: Send + Sync {
+    /// Returns the body stored under `key`, None if it is missing or expired.
+    fn get(&self, key: &str) -> Option<String>;
+    /// Stores a body, replacing any previous one. A `ttl` of None never expires.
+    fn insert(&self, key: String, body: String, ttl: Option<Duration>);
+    fn remove(&self, key: &str);
+    fn clear(&self);
+}
+
+/// Group of endpoints sharing a TTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Endpoint {
+    Account,
+    ActiveShard,
+    /// Summoners of both LoL and TFT
+    Summoner,
+    /// League entries and apex leagues
+    League,
+    ChampionMastery,
+    ChampionRotation,
+    /// Finished matches by ID
+    Match,
+    /// Match IDs by PUUID
+    MatchIds,
+    Timeline,
+    /// Challenge configs, percentiles and leaderboards
+    Challenges,
+    PlayerChallenges,
+    Clash,
+    /// Platform data of every game
+    Status,
+    ActiveGame,
+    FeaturedGames,
+    /// Tournament codes, lobby events and games
+    Tournament,
+    TftLeague,
+    TftMatch,
+    TftMatchIds,
+    ValContent,
+    ValMatch,
+    /// Matchlists and recent matches
+    ValMatchlist,
+    ValRanked,
+    LorMatch,
+    LorMatchIds,
+    LorRanked,
+}
+
+impl Endpoint {
+    /// TTL used unless overridden with `set_ttl`, None if the response never changes.
+    pub fn default_ttl(&self) -> Option<Duration> {
+        let seconds = match self {
+            Endpoint::Match
+            | Endpoint::Timeline
+            | Endpoint::TftMatch
+            | Endpoint::ValMatch
+            | Endpoint::LorMatch => return None,
+            Endpoint::Tournament => 10,
+            Endpoint::Status | Endpoint::ActiveGame => 30,
+            Endpoint::League
+            | Endpoint::MatchIds
+            | Endpoint::TftLeague
+            | Endpoint::TftMatchIds
+            | Endpoint::ValMatchlist
+            | Endpoint::LorMatchIds => 60,
+            Endpoint::FeaturedGames => 120,
+            Endpoint::Summoner
+            | Endpoint::ChampionMastery
+            | Endpoint::PlayerChallenges
+            | Endpoint::Clash
+            | Endpoint::ValRanked
+            | Endpoint::LorRanked => 300,
+            Endpoint::ActiveShard => 600,
+            Endpoint::Account
+            | Endpoint::ChampionRotation
+            | Endpoint::Challenges
+            | Endpoint::ValContent => 3600,
+        };
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// TTL currently in use by `Cache::current`.
+    pub fn ttl(&self) -> Option<Duration> {
+        Cache::current().ttl(*self)
+    }
+}
+
+/// Handle to a response cache, its TTL overrides and the rate limit state of its requests.
+///
+/// Clones share the same state. A new handle caches nothing and uses the default TTLs.
+#[derive(Clone, Default)]
+pub struct Cache {
+    inner: Arc<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    backend: RwLock<Option<Arc<dyn ResponseCache>>>,
+    ttls: RwLock<BTreeMap<Endpoint, Option<Duration>>>,
+    retry_after: RwLock<Option<Instant>>,
+    #[cfg(feature = "disk-cache")]
+    disk: RwLock<Option<DiskCache>>,
+}
+
+static GLOBAL: OnceLock<Cache> = OnceLock::new();
+
+thread_local! {
+    static CURRENT: RefCell<Option<Cache>> = const { RefCell::new(None) };
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle used by requests made outside of any `scope`.
+    pub fn global() -> Cache {
+        GLOBAL.get_or_init(Cache::new).clone()
+    }
+
+    /// Handle of the innermost `scope` being polled, `Cache::global` outside of any.
+    pub fn current() -> Cache {
+        CURRENT
+            .with(|current| current.borrow().clone())
+            .unwrap_or_else(Cache::global)
+    }
+
+    /// Runs `future` with this handle as `Cache::current`, so every request it makes goes
+    /// through this cache. Tasks spawned by `future` don't inherit the scope.
+    pub fn scope<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        Scoped {
+            cache: self.clone(),
+            future: Box::pin(future),
+        }
+    }
+
+    /// Caches every following request in `backend`, replacing any previous one.
+    ///
+    /// Keep a clone of the `Arc` to clear or inspect the cache later.
+    pub fn set_backend(&self, backend: Arc<dyn ResponseCache>) {
+        *self.inner.backend.write().unwrap() = Some(backend);
+    }
+
+    /// Stops caching, returning the backend that was in use.
+    pub fn remove_backend(&self) -> Option<Arc<dyn ResponseCache>> {
+        self.inner.backend.write().unwrap().take()
+    }
+
+    /// Reads match and timeline bodies from `cache` before requesting them, and stores every
+    /// body requested afterwards.
+    #[cfg(feature = "disk-cache")]
+    pub fn set_disk_cache(&self, cache: DiskCache) {
+        *self.inner.disk.write().unwrap() = Some(cache);
+    }
+
+    /// Stops using the disk cache, returning the one that was in use.
+    #[cfg(feature = "disk-cache")]
+    pub fn remove_disk_cache(&self) -> Option<DiskCache> {
+        self.inner.disk.write().unwrap().take()
+    }
+
+    /// Overrides the TTL of an endpoint. None never expires, `Duration::ZERO` disables caching.
+    pub fn set_ttl(&self, endpoint: Endpoint, ttl: Option<Duration>) {
+        self.inner.ttls.write().unwrap().insert(endpoint, ttl);
+    }
+
+    /// Restores the default TTL of every endpoint.
+    pub fn reset_ttls(&self) {
+        self.inner.ttls.write().unwrap().clear();
+    }
+
+    /// TTL of an endpoint, its default unless overridden with `set_ttl`.
+    pub fn ttl(&self, endpoint: Endpoint) -> Option<Duration> {
+        match self.inner.ttls.read().unwrap().get(&endpoint) {
+            Some(ttl) => *ttl,
+            None => endpoint.default_ttl(),
+        }
+    }
+
+    /// Time until which the API asked to stop sending requests, from the `Retry-After` header
+    /// of the last 429 response made through this handle. None if none was rate limited yet.
+    pub fn rate_limited_until(&self) -> Option<Instant> {
+        *self.inner.retry_after.read().unwrap()
+    }
+
+    /// Requests `request_url` through this cache. Cached responses are always
+    /// `StatusCode::OK`.
+    async fn get_with_status(
+        &self,
+        endpoint: Endpoint,
+        request_url: String,
+    ) -> (StatusCode, String) {
+        let ttl = self.ttl(endpoint);
+        let backend = match self.inner.backend.read().unwrap().clone() {
+            Some(backend) if ttl != Some(Duration::ZERO) => Some(backend),
+            _ => None,
+        };
+        let key = cache_key(endpoint, &request_url);
+        if let Some(body) = backend.as_ref().and_then(|backend| backend.get(&key)) {
+            return (StatusCode::OK, body);
+        }
+        let resp = reqwest::get(request_url).await.unwrap();
+        let status = resp.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp
+                .headers()
+                .get("retry-after")
+                .and_then(|value| value.to_str().ok()?.parse().ok())
+                .unwrap_or(1);
+            *self.inner.retry_after.write().unwrap() =
+                Some(Instant::now() + Duration::from_secs(retry_after));
+        }
+        let body = resp.text().await.expect("Could not parse");
+        if let Some(backend) = backend {
+            if status.is_success() {
+                backend.insert(key, body.clone(), ttl);
+            }
+        }
+        (status, body)
+    }
+}
+
+/// Future polled with a handle installed as `Cache::current`.
+struct Scoped<F> {
+    cache: Cache,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // Restores the outer handle even if polling panics
+        struct Restore(Option<Cache>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        let outer = CURRENT.with(|current| current.borrow_mut().replace(self.cache.clone()));
+        let _restore = Restore(outer);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// Caches every following request of `Cache::current` in `cache`, replacing any previous
+/// backend.
+///
+/// Keep a clone of the `Arc` to clear or inspect the cache later.
+pub fn set_cache(cache: Arc<dyn ResponseCache>) {
+    Cache::current().set_backend(cache);
+}
+
+/// Stops caching in `Cache::current`, returning the backend that was in use.
+pub fn remove_cache() -> Option<Arc<dyn ResponseCache>> {
+    Cache::current().remove_backend()
+}
+
+/// Reads match and timeline bodies from `cache` before requesting them, and stores every
+/// body requested afterwards, in `Cache::current`.
+#[cfg(feature = "disk-cache")]
+pub fn set_disk_cache(cache: DiskCache) {
+    Cache::current().set_disk_cache(cache);
+}
+
+/// Stops using the disk cache of `Cache::current`, returning the one that was in use.
+#[cfg(feature = "disk-cache")]
+pub fn remove_disk_cache() -> Option<DiskCache> {
+    Cache::current().remove_disk_cache()
+}
+
+/// Overrides the TTL of an endpoint in `Cache::current`. None never expires, `Duration::ZERO`
+/// disables caching.
+pub fn set_ttl(endpoint: Endpoint, ttl: Option<Duration>) {
+    Cache::current().set_ttl(endpoint, ttl);
+}
+
+/// Restores the default TTL of every endpoint in `Cache::current`.
+pub fn reset_ttls() {
+    Cache::current().reset_ttls();
+}
+
+/// Time until which the API asked `Cache::current` to stop sending requests, see
+/// `Cache::rate_limited_until`.
+pub fn rate_limited_until() -> Option<Instant> {
+    Cache::current().rate_limited_until()
+}
+
+/// Key a response is stored under, e.g.
+/// "Summoner https://euw1.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/...".
+pub fn cache_key(endpoint: Endpoint, request_url: &str) -> String {
+    let url = match request_url.split_once('?') {
+        Some((path, query)) => {
+            let query: Vec<&str> = query
+                .split('&')
+                .filter(|param| !param.starts_with("api_key="))
+                .collect();
+            if query.is_empty() {
+                path.to_string()
+            } else {
+                format!("{}?{}", path, query.join("&"))
+            }
+        }
+        None => request_url.to_string(),
+    };
+    format!("{:?} {}", endpoint, url)
+}
+
+/// Requests `request_url` through `Cache::current`, returning the response body.
+pub(crate) async fn get(endpoint: Endpoint, request_url: String) -> String {
+    get_with_status(endpoint, request_url).await.1
+}
+
+/// Requests `request_url` through `Cache::current`. Cached responses are always
+/// `StatusCode::OK`.
+pub(crate) async fn get_with_status(
+    endpoint: Endpoint,
+    request_url: String,
+) -> (StatusCode, String) {
+    Cache::current()
+        .get_with_status(endpoint, request_url)
+        .await
+}
+
+/// Requests a match-v5 match or timeline through the disk cache of `Cache::current`, if one is
+/// set, and then through its response cache.
+#[cfg_attr(not(feature = "disk-cache"), allow(unused_variables))]
+pub(crate) async fn get_match(endpoint: Endpoint, match_id: &str, request_url: String) -> String {
+    #[cfg(feature = "disk-cache")]
+    {
+        let kind = match endpoint {
+            Endpoint::Timeline => disk::MatchFile::Timeline,
+            _ => disk::MatchFile::Match,
+        };
+        let cache = Cache::current();
+        let disk = cache.inner.disk.read().unwrap().clone();
+        if let Some(body) = disk.as_ref().and_then(|disk| disk.read(kind, match_id)) {
+            return body;
+        }
+        let (status, body) = cache.get_with_status(endpoint, request_url).await;
+        if let Some(disk) = disk {
+            if status.is_success() {
+                if let Err(e) = disk.write(kind, match_id, &body) {
+                    error!("{e:?}, {}", match_id);
+                }
+            }
+        }
+        body
+    }
+    #[cfg(not(feature = "disk-cache"))]
+    get(endpoint, request_url).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMMONER_URL: &str =
+        "https://euw1.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/abc";
+
+    #[test]
+    fn cache_key_strips_api_key() {
+        let key = |url: &str| cache_key(Endpoint::Summoner, url);
+        assert_eq!(
+            key(&format!("{}?api_key=RGAPI-1", SUMMONER_URL)),
+            format!("Summoner {}", SUMMONER_URL)
+        );
+        assert_eq!(
+            key(&format!(
+                "{}?start=0&api_key=RGAPI-1&count=20",
+                SUMMONER_URL
+            )),
+            format!("Summoner {}?start=0&count=20", SUMMONER_URL)
+        );
+        assert_eq!(key(SUMMONER_URL), format!("Summoner {}", SUMMONER_URL));
+        // Keys differ only by endpoint and the remaining query
+        assert_eq!(
+            key(&format!("{}?api_key=RGAPI-1", SUMMONER_URL)),
+            key(&format!("{}?api_key=RGAPI-2", SUMMONER_URL))
+        );
+        assert_ne!(
+            key(SUMMONER_URL),
+            cache_key(Endpoint::Account, SUMMONER_URL)
+        );
+    }
+
+    #[test]
+    fn handles_hold_separate_ttls() {
+        let a = Cache::new();
+        let b = a.clone();
+        let c = Cache::new();
+        a.set_ttl(Endpoint::Summoner, None);
+        assert_eq!(b.ttl(Endpoint::Summoner), None);
+        assert_eq!(c.ttl(Endpoint::Summoner), Endpoint::Summoner.default_ttl());
+        a.reset_ttls();
+        assert_eq!(b.ttl(Endpoint::Summoner), Endpoint::Summoner.default_ttl());
+    }
+
+    #[tokio::test]
+    async fn scopes_set_the_current_handle() {
+        let outer = Cache::new();
+        let inner = Cache::new();
+        inner.set_ttl(Endpoint::League, Some(Duration::ZERO));
+        let ttls = outer
+            .scope(async {
+                set_ttl(Endpoint::League, Some(Duration::from_secs(1)));
+                let nested = inner.scope(async { Endpoint::League.ttl() }).await;
+                (nested, Endpoint::League.ttl())
+            })
+            .await;
+        assert_eq!(ttls, (Some(Duration::ZERO), Some(Duration::from_secs(1))));
+        assert_eq!(outer.ttl(Endpoint::League), Some(Duration::from_secs(1)));
+        assert!(CURRENT.with(|current| current.borrow().is_none()));
+    }
+
+    #[tokio::test]
+    async fn requests_read_the_backend_of_their_scope() {
+        let request_url = format!("{}?api_key=RGAPI-1", SUMMONER_URL);
+        let key = cache_key(Endpoint::Summoner, &request_url);
+        let caches: Vec<Cache> = ["first", "second"]
+            .into_iter()
+            .map(|body| {
+                let backend = Arc::new(LruCache::new(1));
+                backend.insert(key.clone(), body.to_string(), None);
+                let cache = Cache::new();
+                cache.set_backend(backend);
+                cache
+            })
+            .collect();
+        for (cache, body) in caches.iter().zip(["first", "second"]) {
+            let resp = cache.scope(get(Endpoint::Summoner, request_url.clone()));
+            assert_eq!(resp.await, body);
+        }
+        assert!(caches[0].rate_limited_until().is_none());
+    }
+}
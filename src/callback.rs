@@ -0,0 +1,206 @@
+//! Receiver for the game results Riot posts to a tournament provider's callback URL.
+//!
+//! Requires the `callback` feature. A payload can be posted locally for testing with e.g.
+//! `curl -X POST localhost:8080 -H 'Content-Type: application/json' -d @result.json`.
+
+use std::future::Future;
+
+use axum::{body::Bytes, extract::State, http::StatusCode, routing::post, Router};
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+use crate::{
+    lol_match::Match,
+    region::{routing::RoutingRegion, server::ServerRegion},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameResult {
+    /// Unix timestamp in milliseconds
+    pub start_time: u64,
+    /// The tournament code the game was played with
+    pub short_code: String,
+    /// Metadata given when the tournament code was created
+    pub meta_data: Option<String>,
+    pub game_id: u64,
+    pub game_name: String,
+    pub game_type: String,
+    pub game_map: i64,
+    pub game_mode: String,
+    /// Platform ID, e.g. "NA1"
+    pub region: String,
+}
+
+#[derive(Debug)]
+pub enum InvalidGameResult {
+    Json(serde_json::Error),
+    MissingShortCode,
+    UnknownRegion(String),
+}
+
+impl GameResult {
+    /// Parses and validates a callback body.
+    pub fn from_json(body: &[u8]) -> Result<Self, InvalidGameResult> {
+        let result: Self = serde_json::from_slice(body).map_err(InvalidGameResult::Json)?;
+        if result.short_code.is_empty() {
+            return Err(InvalidGameResult::MissingShortCode);
+        }
+        if result.server_region().is_none() {
+            return Err(InvalidGameResult::UnknownRegion(result.region));
+        }
+        Ok(result)
+    }
+
+    /// Platform the game was played on, None if Riot sent a region this crate does not know.
+    pub fn server_region(&self) -> Option<ServerRegion> {
+        ServerRegion::ALL
+            .into_iter()
+            .find(|region| region.to_string().eq_ignore_ascii_case(&self.region))
+    }
+
+    /// Match-v5 ID of the game, e.g. "NA1_1234567890".
+    pub fn match_id(&self) -> String {
+        Match::id_from_game(&self.region, self.game_id)
+    }
+
+    /// Requests the full match, routed through the region the game was played on.
+    pub async fn full_match(&self, api_key: &String) -> Option<Match> {
+        let routing = RoutingRegion::from_server(&self.server_region()?);
+        Match::by_match_id(&routing, api_key, &self.match_id()).await
+    }
+}
+
+/// Builds a router accepting game results on `POST /`, nest it to serve under another path.
+///
+/// Valid payloads are acknowledged immediately and passed to `handler` on a new task,
+/// invalid payloads are rejected with 400 so they show up in Riot's callback logs.
+pub fn router<F, Fut>(handler: F) -> Router
+where
+    F: Fn(GameResult) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    Router::new()
+        .route("/", post(receive::<F, Fut>))
+        .with_state(handler)
+}
+
+/// Serves `router(handler)` on the given address until the listener fails.
+pub async fn serve<A, F, Fut>(addr: A, handler: F) -> std::io::Result<()>
+where
+    A: ToSocketAddrs,
+    F: Fn(GameResult) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, router(handler)).await
+}
+
+async fn receive<F, Fut>(State(handler): State<F>, body: Bytes) -> StatusCode
+where
+    F: Fn(GameResult) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    match GameResult::from_json(&body) {
+        Ok(result) => {
+            tokio::spawn(handler(result));
+            StatusCode::OK
+        }
+        Err(InvalidGameResult::Json(e)) => {
+            error!("{e:?}, {}", String::from_utf8_lossy(&body));
+            StatusCode::BAD_REQUEST
+        }
+        Err(e) => {
+            warn!("Rejected game result callback: {e:?}");
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request};
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const BODY: &str = r#"{
+        "startTime": 1700000000000,
+        "shortCode": "NA04c2c-TOURNAMENTCODE",
+        "metaData": "{\"round\":1}",
+        "gameId": 1234567890,
+        "gameName": "a1b2c3",
+        "gameType": "Practice",
+        "gameMap": 11,
+        "gameMode": "CLASSIC",
+        "region": "NA1"
+    }"#;
+
+    #[test]
+    fn parses_valid_body() {
+        let result = GameResult::from_json(BODY.as_bytes()).unwrap();
+        assert_eq!(result.short_code, "NA04c2c-TOURNAMENTCODE");
+        assert_eq!(result.server_region(), Some(ServerRegion::NA1));
+        assert_eq!(result.match_id(), "NA1_1234567890");
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert!(matches!(
+            GameResult::from_json(b"{\"shortCode\": "),
+            Err(InvalidGameResult::Json(_))
+        ));
+        assert!(matches!(
+            GameResult::from_json(b"{\"shortCode\": \"code\"}"),
+            Err(InvalidGameResult::Json(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_short_code() {
+        let body = BODY.replace("NA04c2c-TOURNAMENTCODE", "");
+        assert!(matches!(
+            GameResult::from_json(body.as_bytes()),
+            Err(InvalidGameResult::MissingShortCode)
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_region() {
+        let body = BODY.replace("\"NA1\"", "\"XX9\"");
+        match GameResult::from_json(body.as_bytes()) {
+            Err(InvalidGameResult::UnknownRegion(region)) => assert_eq!(region, "XX9"),
+            other => panic!("expected an unknown region, got {other:?}"),
+        }
+    }
+
+    fn post(body: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn router_passes_valid_results_to_handler() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let app = router(move |result: GameResult| {
+            let sender = sender.clone();
+            async move {
+                sender.send(result.short_code).unwrap();
+            }
+        });
+
+        let response = app.clone().oneshot(post(BODY)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(receiver.recv().await.unwrap(), "NA04c2c-TOURNAMENTCODE");
+
+        let response = app.oneshot(post("not json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(receiver.try_recv().is_err());
+    }
+}
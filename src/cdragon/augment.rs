@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ddragon::{StaticEntry, StaticIndex};
+
+pub type AugmentIndex = StaticIndex<i64, Augment>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Augment {
+    pub id: i64,
+    pub api_name: String,
+    pub name: String,
+    pub desc: String,
+    pub tooltip: String,
+    /// Game asset path, resolvable through `CommunityDragon::game_asset_url`
+    pub icon_large: String,
+    pub icon_small: String,
+    /// 0 = silver, 1 = gold, 2 = prismatic
+    pub rarity: i64,
+    #[serde(default)]
+    pub data_values: HashMap<String, f64>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AugmentFile {
+    augments: Vec<Augment>,
+}
+
+impl StaticEntry for Augment {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.icon_large.clone())
+    }
+}
+
+pub(crate) fn index(version: String, file: AugmentFile) -> AugmentIndex {
+    let entries = file
+        .augments
+        .into_iter()
+        .map(|augment| (augment.id, augment))
+        .collect();
+    StaticIndex { version, entries }
+}
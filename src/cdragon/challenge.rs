@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    challenges::ChallengeLevel,
+    ddragon::{StaticEntry, StaticIndex},
+};
+
+pub type ChallengeAssetIndex = StaticIndex<i64, ChallengeAsset>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeAsset {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub description_short: Option<String>,
+    /// Level name, e.g. "GOLD", to client asset path of that level's token
+    #[serde(default)]
+    pub level_to_icon_path: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ChallengeFile {
+    challenges: HashMap<String, ChallengeAsset>,
+}
+
+impl ChallengeAsset {
+    /// Client asset path of the token for the given level.
+    pub fn icon_for(&self, level: ChallengeLevel) -> Option<&String> {
+        self.level_to_icon_path.get(&level.to_string())
+    }
+}
+
+impl StaticEntry for ChallengeAsset {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Token of the highest level the challenge has an icon for
+    fn icon_path(&self) -> Option<String> {
+        [
+            ChallengeLevel::CHALLENGER,
+            ChallengeLevel::GRANDMASTER,
+            ChallengeLevel::MASTER,
+            ChallengeLevel::DIAMOND,
+            ChallengeLevel::PLATINUM,
+            ChallengeLevel::GOLD,
+            ChallengeLevel::SILVER,
+            ChallengeLevel::BRONZE,
+            ChallengeLevel::IRON,
+        ]
+        .into_iter()
+        .find_map(|level| self.icon_for(level).cloned())
+    }
+}
+
+pub(crate) fn index(version: String, file: ChallengeFile) -> ChallengeAssetIndex {
+    let entries = file
+        .challenges
+        .into_values()
+        .map(|challenge| (challenge.id, challenge))
+        .collect();
+    StaticIndex { version, entries }
+}
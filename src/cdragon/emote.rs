@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ddragon::{StaticEntry, StaticIndex};
+
+pub type EmoteIndex = StaticIndex<i64, Emote>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Emote {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Client asset path, resolvable through `CommunityDragon::asset_url`
+    pub inventory_icon: String,
+}
+
+impl StaticEntry for Emote {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.inventory_icon.clone())
+    }
+}
+
+pub(crate) fn index(version: String, emotes: Vec<Emote>) -> EmoteIndex {
+    let entries = emotes.into_iter().map(|emote| (emote.id, emote)).collect();
+    StaticIndex { version, entries }
+}
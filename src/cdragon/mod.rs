@@ -0,0 +1,250 @@
+//! Client for CommunityDragon, which extracts data Data Dragon does not publish such as arena
+//! augments, challenge icons, rune stat labels, emotes and ward skins.
+//!
+//! Indexes are `StaticIndex`es, so they share the `Lookup` and `StaticEntry` traits with the
+//! Data Dragon indexes.
+
+pub mod augment;
+pub mod challenge;
+pub mod emote;
+pub mod perk;
+pub mod ward_skin;
+
+use crate::ddragon::fetch;
+
+use self::{
+    augment::AugmentIndex, challenge::ChallengeAssetIndex, emote::EmoteIndex, perk::PerkIndex,
+    ward_skin::WardSkinIndex,
+};
+
+pub const DEFAULT_HOST: &str = "https://raw.communitydragon.org";
+
+#[derive(Clone, Debug)]
+pub struct CommunityDragon {
+    /// Base URL such as `DEFAULT_HOST` or a stub server, or a `file://` path to a directory
+    /// laid out like the CDN
+    pub host: String,
+    /// Patch directory to read from, e.g. "latest", "pbe" or "14.23"
+    pub version: String,
+}
+
+impl Default for CommunityDragon {
+    fn default() -> Self {
+        Self {
+            host: String::from(DEFAULT_HOST),
+            version: String::from("latest"),
+        }
+    }
+}
+
+impl CommunityDragon {
+    pub fn new(host: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            host: host.into().trim_end_matches('/').to_string(),
+            version: version.into(),
+        }
+    }
+
+    /// Requests the arena augments, matching `Participant.player_augment_1..4`.
+    pub async fn arena_augments(&self, locale: &str) -> Option<AugmentIndex> {
+        self.augments(&format!("cdragon/arena/{}.json", locale.to_lowercase()))
+            .await
+    }
+
+    /// Requests augments from a file in the arena augment format, relative to the version
+    /// directory, for game modes published after this crate.
+    pub async fn augments(&self, path: &str) -> Option<AugmentIndex> {
+        let file = fetch(&self.host, &format!("{}/{}", self.version, path)).await?;
+        Some(augment::index(self.version.clone(), file))
+    }
+
+    /// Requests challenge names and level icons, matching `ChallengeProgress.challenge_id`.
+    pub async fn challenges(&self, locale: &str) -> Option<ChallengeAssetIndex> {
+        let file = fetch(&self.host, &self.game_data_path(locale, "challenges.json")).await?;
+        Some(challenge::index(self.version.clone(), file))
+    }
+
+    pub async fn emotes(&self, locale: &str) -> Option<EmoteIndex> {
+        let emotes = fetch(
+            &self.host,
+            &self.game_data_path(locale, "summoner-emotes.json"),
+        )
+        .await?;
+        Some(emote::index(self.version.clone(), emotes))
+    }
+
+    /// Requests runes and stat shards, including the labels of `PerkStyleSelection.var1..3`
+    /// which Data Dragon does not publish.
+    pub async fn perks(&self, locale: &str) -> Option<PerkIndex> {
+        let perks = fetch(&self.host, &self.game_data_path(locale, "perks.json")).await?;
+        Some(perk::index(self.version.clone(), perks))
+    }
+
+    pub async fn ward_skins(&self, locale: &str) -> Option<WardSkinIndex> {
+        let skins = fetch(&self.host, &self.game_data_path(locale, "wardskins.json")).await?;
+        Some(ward_skin::index(self.version.clone(), skins))
+    }
+
+    /// URL of a client asset path such as "/lol-game-data/assets/ASSETS/Challenges/...",
+    /// as used by challenges, emotes and ward skins.
+    pub fn asset_url(&self, path: &str) -> String {
+        let path = path
+            .strip_prefix("/lol-game-data/assets/")
+            .unwrap_or(path)
+            .to_lowercase();
+        format!(
+            "{}/{}/plugins/rcp-be-lol-game-data/global/default/{}",
+            self.host, self.version, path
+        )
+    }
+
+    /// URL of a game asset path such as "assets/ux/cherry/augments/icons/...", as used by
+    /// augments.
+    pub fn game_asset_url(&self, path: &str) -> String {
+        let path = path.to_lowercase();
+        let path = match path.strip_suffix(".tex") {
+            Some(path) => format!("{}.png", path),
+            None => path,
+        };
+        format!("{}/{}/game/{}", self.host, self.version, path)
+    }
+
+    fn game_data_path(&self, locale: &str, file: &str) -> String {
+        let locale = match locale.to_lowercase().as_str() {
+            "en_us" | "default" => String::from("default"),
+            locale => locale.to_string(),
+        };
+        format!(
+            "{}/plugins/rcp-be-lol-game-data/global/{}/v1/{}",
+            self.version, locale, file
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::ddragon::Lookup;
+
+    fn write(root: &Path, path: &str, value: Value) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value.to_string()).unwrap();
+    }
+
+    #[test]
+    fn builds_game_data_paths() {
+        let cdragon = CommunityDragon::new("https://example.com/", "14.23");
+        assert_eq!(
+            cdragon.game_data_path("en_US", "perks.json"),
+            "14.23/plugins/rcp-be-lol-game-data/global/default/v1/perks.json"
+        );
+        assert_eq!(
+            cdragon.game_data_path("default", "perks.json"),
+            "14.23/plugins/rcp-be-lol-game-data/global/default/v1/perks.json"
+        );
+        assert_eq!(
+            cdragon.game_data_path("ko_KR", "challenges.json"),
+            "14.23/plugins/rcp-be-lol-game-data/global/ko_kr/v1/challenges.json"
+        );
+    }
+
+    #[test]
+    fn builds_asset_urls() {
+        let cdragon = CommunityDragon::new("https://example.com/", "latest");
+        assert_eq!(
+            cdragon.asset_url("/lol-game-data/assets/ASSETS/Challenges/Config/1/Tokens/GOLD.png"),
+            "https://example.com/latest/plugins/rcp-be-lol-game-data/global/default/\
+             assets/challenges/config/1/tokens/gold.png"
+        );
+        assert_eq!(
+            cdragon.asset_url("v1/perk-images/Styles/Domination/Electrocute.png"),
+            "https://example.com/latest/plugins/rcp-be-lol-game-data/global/default/\
+             v1/perk-images/styles/domination/electrocute.png"
+        );
+        assert_eq!(
+            cdragon.game_asset_url("ASSETS/UX/Cherry/Augments/Icons/Typhoon_large.TEX"),
+            "https://example.com/latest/game/assets/ux/cherry/augments/icons/typhoon_large.png"
+        );
+        assert_eq!(
+            cdragon.game_asset_url("assets/ux/cherry/augments/icons/typhoon_small.png"),
+            "https://example.com/latest/game/assets/ux/cherry/augments/icons/typhoon_small.png"
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_perks_and_augments_from_a_local_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let cdragon = CommunityDragon::new(format!("file://{}", dir.path().display()), "14.23");
+        write(
+            dir.path(),
+            &cdragon.game_data_path("en_US", "perks.json"),
+            json!([
+                {
+                    "id": 8112,
+                    "name": "Electrocute",
+                    "iconPath": "/lol-game-data/assets/v1/perk-images/Electrocute.png",
+                    "endOfGameStatDescs": ["Total Damage Dealt: @eogvar1@"],
+                },
+                {
+                    "id": 8009,
+                    "name": "Presence of Mind",
+                    "shortDesc": "s",
+                    "iconPath": "/lol-game-data/assets/v1/perk-images/PresenceOfMind.png",
+                    "endOfGameStatDescs": [
+                        "Resource Restored: @eogvar1@",
+                        "Time @eogvar2@:@eogvar3@",
+                    ],
+                },
+            ]),
+        );
+        write(
+            dir.path(),
+            "14.23/cdragon/arena/en_us.json",
+            json!({
+                "augments": [
+                    {
+                        "id": 1,
+                        "apiName": "Typhoon",
+                        "name": "Typhoon",
+                        "desc": "d",
+                        "tooltip": "t",
+                        "iconLarge": "assets/ux/cherry/augments/icons/typhoon_large.tex",
+                        "iconSmall": "assets/ux/cherry/augments/icons/typhoon_small.tex",
+                        "rarity": 1,
+                        "dataValues": {"Damage": 10.0},
+                    },
+                ],
+            }),
+        );
+
+        let perks = cdragon.perks("en_US").await.unwrap();
+        assert_eq!(perks.version, "14.23");
+        assert_eq!(perks.name_of(8112), Some("Electrocute"));
+        let presence_of_mind = perks.lookup(8009).unwrap();
+        assert_eq!(
+            presence_of_mind.stat_descs([250, 12, 30]),
+            vec!["Resource Restored: 250", "Time 12:30"]
+        );
+        assert_eq!(
+            perks.lookup(8112).unwrap().stat_descs([1234, 0, 0]),
+            vec!["Total Damage Dealt: 1234"]
+        );
+
+        let augments = cdragon.arena_augments("en_US").await.unwrap();
+        let typhoon = augments.lookup(1).unwrap();
+        assert_eq!(typhoon.rarity, 1);
+        assert_eq!(
+            cdragon.game_asset_url(&typhoon.icon_large),
+            format!(
+                "{}/14.23/game/assets/ux/cherry/augments/icons/typhoon_large.png",
+                cdragon.host
+            )
+        );
+        assert!(cdragon.ward_skins("en_US").await.is_none());
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ddragon::{StaticEntry, StaticIndex};
+
+pub type PerkIndex = StaticIndex<i64, Perk>;
+
+/// A rune or stat shard, matching `PerkStyleSelection.perk` and the `PerkStats` IDs.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Perk {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub short_desc: String,
+    #[serde(default)]
+    pub long_desc: String,
+    /// Client asset path, resolvable through `CommunityDragon::asset_url`
+    pub icon_path: String,
+    /// Labels of `var1..3`, e.g. "Total Damage Dealt: @eogvar1@"
+    #[serde(default)]
+    pub end_of_game_stat_descs: Vec<String>,
+}
+
+impl Perk {
+    /// Fills the end of game stat labels with the given `var1..3` values.
+    pub fn stat_descs(&self, vars: [i64; 3]) -> Vec<String> {
+        self.end_of_game_stat_descs
+            .iter()
+            .map(|desc| {
+                vars.iter()
+                    .enumerate()
+                    .fold(desc.clone(), |desc, (i, var)| {
+                        desc.replace(&format!("@eogvar{}@", i + 1), &var.to_string())
+                    })
+            })
+            .collect()
+    }
+}
+
+impl StaticEntry for Perk {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.icon_path.clone())
+    }
+}
+
+pub(crate) fn index(version: String, perks: Vec<Perk>) -> PerkIndex {
+    let entries = perks.into_iter().map(|perk| (perk.id, perk)).collect();
+    StaticIndex { version, entries }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ddragon::{StaticEntry, StaticIndex};
+
+pub type WardSkinIndex = StaticIndex<i64, WardSkin>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WardSkin {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Client asset path, resolvable through `CommunityDragon::asset_url`
+    pub ward_image_path: String,
+    pub ward_shadow_image_path: Option<String>,
+    #[serde(default)]
+    pub is_legacy: bool,
+}
+
+impl StaticEntry for WardSkin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.ward_image_path.clone())
+    }
+}
+
+pub(crate) fn index(version: String, skins: Vec<WardSkin>) -> WardSkinIndex {
+    let entries = skins.into_iter().map(|skin| (skin.id, skin)).collect();
+    StaticIndex { version, entries }
+}
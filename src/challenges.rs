@@ -0,0 +1,281 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::{
+    cache::{self, Endpoint},
+    region::server::ServerRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+/// Challenge levels in ascending order, unlike ranked tiers there is no EMERALD.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ChallengeLevel {
+    NONE,
+    IRON,
+    BRONZE,
+    SILVER,
+    GOLD,
+    PLATINUM,
+    DIAMOND,
+    MASTER,
+    GRANDMASTER,
+    CHALLENGER,
+}
+
+impl fmt::Display for ChallengeLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::NONE => write!(f, "NONE"),
+            Self::IRON => write!(f, "IRON"),
+            Self::BRONZE => write!(f, "BRONZE"),
+            Self::SILVER => write!(f, "SILVER"),
+            Self::GOLD => write!(f, "GOLD"),
+            Self::PLATINUM => write!(f, "PLATINUM"),
+            Self::DIAMOND => write!(f, "DIAMOND"),
+            Self::MASTER => write!(f, "MASTER"),
+            Self::GRANDMASTER => write!(f, "GRANDMASTER"),
+            Self::CHALLENGER => write!(f, "CHALLENGER"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeState {
+    DISABLED,
+    HIDDEN,
+    ENABLED,
+    ARCHIVED,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeTracking {
+    LIFETIME,
+    SEASON,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeConfig {
+    pub id: i64,
+    /// Locale (e.g. "en_US") to the challenge's names in that locale
+    pub localized_names: HashMap<String, LocalizedChallengeName>,
+    pub state: ChallengeState,
+    pub tracking: Option<ChallengeTracking>,
+    /// Unix timestamp in milliseconds
+    pub start_timestamp: Option<u64>,
+    /// Unix timestamp in milliseconds
+    pub end_timestamp: Option<u64>,
+    pub leaderboard: bool,
+    /// Value required to reach each level
+    pub thresholds: HashMap<ChallengeLevel, f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedChallengeName {
+    pub name: String,
+    pub description: String,
+    pub short_description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ApexPlayer {
+    pub puuid: String,
+    pub value: f64,
+    pub position: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerChallenges {
+    pub challenges: Vec<ChallengeProgress>,
+    pub preferences: ChallengePreferences,
+    /// Overall challenge points, the level is the player's crystal
+    pub total_points: ChallengePoints,
+    /// Category name (e.g. "COLLECTION") to the points earned in it
+    pub category_points: HashMap<String, ChallengePoints>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeProgress {
+    pub challenge_id: i64,
+    pub percentile: f64,
+    pub level: ChallengeLevel,
+    pub value: f64,
+    /// Unix timestamp in milliseconds
+    pub achieved_time: Option<u64>,
+    /// Only present for challenges with a leaderboard
+    pub position: Option<i64>,
+    pub players_in_level: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengePoints {
+    pub level: ChallengeLevel,
+    pub current: i64,
+    pub max: i64,
+    pub percentile: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengePreferences {
+    pub banner_accent: Option<String>,
+    /// Item ID of the equipped title, empty if none
+    pub title: Option<String>,
+    /// Challenges showcased on the player's profile
+    #[serde(default)]
+    pub challenge_ids: Vec<i64>,
+    pub crest_border: Option<String>,
+    pub prestige_crest_border_level: Option<i64>,
+}
+
+impl ChallengeConfig {
+    /// Requests the configuration of every challenge.
+    pub async fn all(region: &ServerRegion, api_key: &String) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/challenges/v1/challenges/config?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::Challenges, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn by_challenge_id(
+        region: &ServerRegion,
+        api_key: &String,
+        challenge_id: i64,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/challenges/v1/challenges/{}/config?api_key={}",
+            region, challenge_id, api_key
+        );
+        let resp = cache::get(Endpoint::Challenges, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the percentage of players at each level of every challenge, keyed by challenge ID.
+    pub async fn all_percentiles(
+        region: &ServerRegion,
+        api_key: &String,
+    ) -> Option<HashMap<i64, HashMap<ChallengeLevel, f64>>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/challenges/v1/challenges/percentiles?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::Challenges, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the percentage of players at each level of a challenge.
+    pub async fn percentiles(
+        region: &ServerRegion,
+        api_key: &String,
+        challenge_id: i64,
+    ) -> Option<HashMap<ChallengeLevel, f64>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/challenges/v1/challenges/{}/percentiles?api_key={}",
+            region, challenge_id, api_key
+        );
+        let resp = cache::get(Endpoint::Challenges, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the top players of a challenge at the given level.
+    ///
+    /// Only MASTER, GRANDMASTER and CHALLENGER have leaderboards.
+    pub async fn leaderboard(
+        region: &ServerRegion,
+        api_key: &String,
+        challenge_id: i64,
+        level: ChallengeLevel,
+        limit: Option<u32>,
+    ) -> Option<Vec<ApexPlayer>> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/lol/challenges/v1/challenges/{}/leaderboards/by-level/{}?api_key={}",
+            region, challenge_id, level, api_key
+        );
+        if let Some(limit) = limit {
+            request_url = format!("{}&limit={}", request_url, limit);
+        }
+        let resp = cache::get(Endpoint::Challenges, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Name of the challenge in the given locale, e.g. "en_US".
+    pub fn name(&self, locale: &str) -> Option<&String> {
+        self.localized_names.get(locale).map(|names| &names.name)
+    }
+
+    /// Highest level whose threshold `value` reaches, NONE if below every threshold.
+    pub fn level_for(&self, value: f64) -> ChallengeLevel {
+        self.thresholds
+            .iter()
+            .filter(|(_, threshold)| value >= **threshold)
+            .map(|(level, _)| *level)
+            .max()
+            .unwrap_or(ChallengeLevel::NONE)
+    }
+}
+
+impl PlayerChallenges {
+    pub async fn by_puuid(region: &ServerRegion, api_key: &String, puuid: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/challenges/v1/player-data/{}?api_key={}",
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::PlayerChallenges, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Level of the player's overall challenge crystal.
+    pub fn crystal(&self) -> ChallengeLevel {
+        self.total_points.level
+    }
+
+    pub fn challenge(&self, challenge_id: i64) -> Option<&ChallengeProgress> {
+        self.challenges
+            .iter()
+            .find(|challenge| challenge.challenge_id == challenge_id)
+    }
+}
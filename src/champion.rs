@@ -0,0 +1,159 @@
+use core::fmt;
+use std::collections::HashSet;
+
+use crate::{
+    cache::{self, Endpoint},
+    region::server::ServerRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+/// Numeric champion key as used across the Riot API, e.g. 266 for Aatrox.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct ChampionId(pub i64);
+
+impl fmt::Display for ChampionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<i64> for ChampionId {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionRotation {
+    pub free_champion_ids: Vec<ChampionId>,
+    pub free_champion_ids_for_new_players: Vec<ChampionId>,
+    /// Players at or below this level get the new player rotation instead
+    pub max_new_player_level: i64,
+}
+
+/// Difference between two free-to-play rotations.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct RotationChange {
+    /// Champions free in the current rotation that were not free before
+    pub now_free: Vec<ChampionId>,
+    /// Champions that were free before but are no longer
+    pub no_longer_free: Vec<ChampionId>,
+    pub now_free_for_new_players: Vec<ChampionId>,
+    pub no_longer_free_for_new_players: Vec<ChampionId>,
+}
+
+impl ChampionRotation {
+    pub async fn get(region: &ServerRegion, api_key: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/platform/v3/champion-rotations?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::ChampionRotation, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Compares this rotation against an older one.
+    pub fn diff(&self, previous: &ChampionRotation) -> RotationChange {
+        let (now_free, no_longer_free) =
+            diff_ids(&previous.free_champion_ids, &self.free_champion_ids);
+        let (now_free_for_new_players, no_longer_free_for_new_players) = diff_ids(
+            &previous.free_champion_ids_for_new_players,
+            &self.free_champion_ids_for_new_players,
+        );
+        RotationChange {
+            now_free,
+            no_longer_free,
+            now_free_for_new_players,
+            no_longer_free_for_new_players,
+        }
+    }
+
+    pub fn is_free(&self, champion_id: ChampionId) -> bool {
+        self.free_champion_ids.contains(&champion_id)
+    }
+}
+
+impl RotationChange {
+    pub fn is_empty(&self) -> bool {
+        self.now_free.is_empty()
+            && self.no_longer_free.is_empty()
+            && self.now_free_for_new_players.is_empty()
+            && self.no_longer_free_for_new_players.is_empty()
+    }
+}
+
+/// Returns (added, removed) going from `old` to `new`, in the order they appear.
+fn diff_ids(old: &[ChampionId], new: &[ChampionId]) -> (Vec<ChampionId>, Vec<ChampionId>) {
+    let old_set: HashSet<&ChampionId> = old.iter().collect();
+    let new_set: HashSet<&ChampionId> = new.iter().collect();
+    let added = new
+        .iter()
+        .filter(|id| !old_set.contains(id))
+        .copied()
+        .collect();
+    let removed = old
+        .iter()
+        .filter(|id| !new_set.contains(id))
+        .copied()
+        .collect();
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[i64]) -> Vec<ChampionId> {
+        ids.iter().copied().map(ChampionId).collect()
+    }
+
+    fn rotation(free: &[i64], new_players: &[i64]) -> ChampionRotation {
+        ChampionRotation {
+            free_champion_ids: ids(free),
+            free_champion_ids_for_new_players: ids(new_players),
+            max_new_player_level: 10,
+        }
+    }
+
+    #[test]
+    fn diff_ids_keeps_order() {
+        let (added, removed) = diff_ids(&ids(&[1, 2, 3, 4]), &ids(&[5, 3, 1, 6]));
+        assert_eq!(added, ids(&[5, 6]));
+        assert_eq!(removed, ids(&[2, 4]));
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_champions() {
+        let previous = rotation(&[1, 2, 3], &[10, 11]);
+        let current = rotation(&[2, 3, 4], &[10, 11, 12]);
+        let change = current.diff(&previous);
+        assert_eq!(
+            change,
+            RotationChange {
+                now_free: ids(&[4]),
+                no_longer_free: ids(&[1]),
+                now_free_for_new_players: ids(&[12]),
+                no_longer_free_for_new_players: vec![],
+            }
+        );
+        assert!(!change.is_empty());
+        assert!(current.is_free(ChampionId(4)));
+        assert!(!current.is_free(ChampionId(1)));
+    }
+
+    #[test]
+    fn diff_of_same_rotation_is_empty() {
+        let previous = rotation(&[1, 2, 3], &[10]);
+        let current = rotation(&[3, 2, 1], &[10]);
+        assert!(current.diff(&previous).is_empty());
+    }
+}
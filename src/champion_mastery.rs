@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use crate::{
+    cache::{self, Endpoint},
+    champion::ChampionId,
+    region::server::ServerRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionMastery {
+    pub puuid: String,
+    pub champion_id: ChampionId,
+    pub champion_level: i64,
+    pub champion_points: i64,
+    /// Unix timestamp in milliseconds
+    pub last_play_time: u64,
+    pub champion_points_since_last_level: i64,
+    /// Zero once the champion is at max level
+    pub champion_points_until_next_level: i64,
+    /// No longer sent since the removal of hextech chests
+    pub chest_granted: Option<bool>,
+    pub tokens_earned: i64,
+    pub mark_required_for_next_level: Option<i64>,
+    pub champion_season_milestone: Option<i64>,
+    pub next_season_milestone: Option<NextSeasonMilestone>,
+    #[serde(default)]
+    pub milestone_grades: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NextSeasonMilestone {
+    /// Grade (e.g. "S-") to number of games required at that grade
+    pub require_grade_counts: HashMap<String, i64>,
+    pub reward_marks: i64,
+    pub bonus: bool,
+    pub reward_config: Option<RewardConfig>,
+    pub total_games_requires: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardConfig {
+    pub reward_value: String,
+    pub reward_type: String,
+    pub maximum_reward: i64,
+}
+
+impl ChampionMastery {
+    /// Requests every champion mastery entry for the given player, sorted by
+    /// champion points descending.
+    pub async fn by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}?api_key={}",
+            region,
+            puuid,
+            api_key
+        );
+        let resp = cache::get(Endpoint::ChampionMastery, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Returns None on error or if the player has never played the champion.
+    pub async fn by_puuid_and_champion(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+        champion_id: ChampionId,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}/by-champion/{}?api_key={}",
+            region,
+            puuid,
+            champion_id,
+            api_key
+        );
+        let resp = cache::get(Endpoint::ChampionMastery, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the player's highest mastery entries, the API defaults to 3
+    /// when `count` is None.
+    pub async fn top_by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+        count: Option<u32>,
+    ) -> Option<Vec<Self>> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/lol/champion-mastery/v4/champion-masteries/by-puuid/{}/top?api_key={}",
+            region,
+            puuid,
+            api_key
+        );
+        if let Some(count) = count {
+            request_url = format!("{}&count={}", request_url, count);
+        }
+        let resp = cache::get(Endpoint::ChampionMastery, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the sum of the player's champion mastery levels.
+    pub async fn score_by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<i64> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/champion-mastery/v4/scores/by-puuid/{}?api_key={}",
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::ChampionMastery, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    cache::{self, Endpoint},
+    lol_match::Match,
+    region::{routing::RoutingRegion, server::ServerRegion},
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClashPosition {
+    Unselected,
+    Fill,
+    Top,
+    Jungle,
+    Middle,
+    Bottom,
+    Utility,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClashRole {
+    Captain,
+    Member,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClashPlayer {
+    pub summoner_id: Option<String>,
+    pub puuid: String,
+    /// None if the player is registered but not on a team yet
+    pub team_id: Option<String>,
+    pub position: ClashPosition,
+    pub role: ClashRole,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClashTeam {
+    pub id: String,
+    pub tournament_id: i64,
+    pub name: String,
+    pub icon_id: i64,
+    pub tier: i64,
+    /// Summoner ID of the team captain
+    pub captain: Option<String>,
+    pub abbreviation: String,
+    pub players: Vec<ClashPlayer>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClashTournament {
+    pub id: i64,
+    pub theme_id: i64,
+    pub name_key: String,
+    pub name_key_secondary: String,
+    /// Tournament phases, e.g. one per day of a two day clash
+    pub schedule: Vec<TournamentPhase>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentPhase {
+    pub id: i64,
+    /// Unix timestamp in milliseconds
+    pub registration_time: u64,
+    /// Unix timestamp in milliseconds
+    pub start_time: u64,
+    pub cancelled: bool,
+}
+
+impl ClashPlayer {
+    /// Requests the clash registrations of the given player, one per active or upcoming tournament.
+    pub async fn by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/clash/v1/players/by-puuid/{}?api_key={}",
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::Clash, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl ClashTeam {
+    pub async fn by_team_id(
+        region: &ServerRegion,
+        api_key: &String,
+        team_id: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/clash/v1/teams/{}?api_key={}",
+            region, team_id, api_key
+        );
+        let resp = cache::get(Endpoint::Clash, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the most recent match IDs of every team member, in team order.
+    ///
+    /// Members whose lookup failed are paired with None.
+    pub async fn match_histories(
+        &self,
+        region: &ServerRegion,
+        api_key: &String,
+        count: Option<i64>,
+    ) -> Vec<(&ClashPlayer, Option<Vec<String>>)> {
+        let routing = RoutingRegion::from_server(region);
+        let mut histories = Vec::with_capacity(self.players.len());
+        for player in &self.players {
+            let ids = Match::by_puuid(
+                &routing,
+                api_key,
+                &player.puuid,
+                None,
+                None,
+                None,
+                None,
+                None,
+                count,
+            )
+            .await;
+            histories.push((player, ids));
+        }
+        histories
+    }
+}
+
+impl ClashTournament {
+    /// Requests all active or upcoming tournaments.
+    pub async fn tournaments(region: &ServerRegion, api_key: &String) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/clash/v1/tournaments?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::Clash, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn by_team_id(
+        region: &ServerRegion,
+        api_key: &String,
+        team_id: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/clash/v1/tournaments/by-team/{}?api_key={}",
+            region, team_id, api_key
+        );
+        let resp = cache::get(Endpoint::Clash, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn by_tournament_id(
+        region: &ServerRegion,
+        api_key: &String,
+        tournament_id: i64,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/clash/v1/tournaments/{}?api_key={}",
+            region, tournament_id, api_key
+        );
+        let resp = cache::get(Endpoint::Clash, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// The first phase that has not been cancelled and starts after `now` (Unix milliseconds).
+    pub fn next_phase(&self, now: u64) -> Option<&TournamentPhase> {
+        self.schedule
+            .iter()
+            .filter(|phase| !phase.cancelled && phase.start_time > now)
+            .min_by_key(|phase| phase.start_time)
+    }
+}
@@ -0,0 +1,625 @@
+//! Ladder crawler collecting match IDs from the players of chosen ranked tiers.
+//!
+//! The crawler walks apex leagues and league entry pages, resolves every player to a PUUID
+//! and samples their recent matches, deduplicating match IDs across players. Its whole state
+//! is a serializable `CrawlFrontier`, so a crawl can be saved after any step and resumed.
+//! Sources and players whose requests fail are kept in the frontier to be retried later.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+use futures::future::join_all;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::{
+    cache,
+    league::{LeagueEntry, LeagueList},
+    lol_match::Match,
+    queue::ranked::RankedQueue,
+    ranked::{division::RankedDivision, tier::RankedTier},
+    region::{routing::RoutingRegion, server::ServerRegion},
+    summoner::Summoner,
+};
+
+/// Requests allowed within a window of time.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub requests: usize,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// Limits of a development API key, 20 requests per second and 100 per two minutes.
+    pub const DEVELOPMENT: [RateLimit; 2] = [
+        RateLimit {
+            requests: 20,
+            per: Duration::from_secs(1),
+        },
+        RateLimit {
+            requests: 100,
+            per: Duration::from_secs(120),
+        },
+    ];
+}
+
+/// Spaces requests to stay within every limit, and waits out 429 responses.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limits: Vec<RateLimit>,
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: Vec<RateLimit>) -> Self {
+        Self {
+            limits,
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Waits until a request can be sent and records it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait_until = {
+                let now = Instant::now();
+                let mut sent = self.sent.lock().unwrap();
+                let longest = self.limits.iter().map(|limit| limit.per).max();
+                while let (Some(first), Some(longest)) = (sent.front(), longest) {
+                    if now.duration_since(*first) < longest {
+                        break;
+                    }
+                    sent.pop_front();
+                }
+                let mut wait_until = cache::rate_limited_until()
+                    .map(Instant::from_std)
+                    .filter(|until| *until > now);
+                for limit in &self.limits {
+                    let in_window = sent
+                        .iter()
+                        .filter(|time| now.duration_since(**time) < limit.per)
+                        .count();
+                    if limit.requests > 0 && in_window >= limit.requests {
+                        // The oldest request in the window has to leave it first
+                        let oldest = sent[sent.len() - in_window];
+                        let until = oldest + limit.per;
+                        wait_until = Some(wait_until.map_or(until, |wait| wait.max(until)));
+                    }
+                }
+                if wait_until.is_none() {
+                    sent.push_back(now);
+                }
+                wait_until
+            };
+            match wait_until {
+                Some(until) => tokio::time::sleep_until(until).await,
+                None => return,
+            }
+        }
+    }
+}
+
+/// Where players are read from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum LadderSource {
+    /// A whole MASTER, GRANDMASTER or CHALLENGER league
+    Apex(RankedTier),
+    /// A page of `LeagueEntry::entries`, starting at 1
+    Entries {
+        tier: RankedTier,
+        division: RankedDivision,
+        page: u32,
+    },
+}
+
+impl LadderSource {
+    pub fn tier(&self) -> RankedTier {
+        match self {
+            Self::Apex(tier) => *tier,
+            Self::Entries { tier, .. } => *tier,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LadderPlayer {
+    pub tier: RankedTier,
+    pub summoner_id: String,
+    pub puuid: Option<String>,
+}
+
+/// Remaining work and results of a crawl.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CrawlFrontier {
+    /// Sources not read yet, in order
+    pub sources: VecDeque<LadderSource>,
+    /// Players read from sources whose matches have not been sampled yet
+    pub players: VecDeque<LadderPlayer>,
+    /// Players queued per tier, counted against `CrawlConfig::players_per_tier`
+    pub queued: HashMap<RankedTier, usize>,
+    /// Summoner IDs of every queued player
+    pub seen_players: HashSet<String>,
+    /// Every match ID returned so far
+    pub seen_matches: HashSet<String>,
+    /// Sources that could not be read, see `retry_failed`
+    #[serde(default)]
+    pub failed_sources: Vec<LadderSource>,
+    /// Players whose PUUID or matches could not be requested, see `retry_failed`
+    #[serde(default)]
+    pub failed_players: Vec<LadderPlayer>,
+}
+
+impl CrawlFrontier {
+    /// Starts a crawl of the given tiers, apex tiers through their league and every other
+    /// tier through the entry pages of each division.
+    pub fn new(tiers: &[RankedTier]) -> Self {
+        let mut frontier = Self::default();
+        for tier in tiers {
+            if tier.is_apex() {
+                frontier.sources.push_back(LadderSource::Apex(*tier));
+            } else {
+                for division in RankedDivision::ALL {
+                    frontier.sources.push_back(LadderSource::Entries {
+                        tier: *tier,
+                        division,
+                        page: 1,
+                    });
+                }
+            }
+        }
+        frontier
+    }
+
+    /// Whether every source and player was read or failed.
+    pub fn is_done(&self) -> bool {
+        self.sources.is_empty() && self.players.is_empty()
+    }
+
+    /// Queues the failed sources and players again, after the remaining ones. Returns the
+    /// number of requeued sources and players.
+    pub fn retry_failed(&mut self) -> usize {
+        let failed = self.failed_sources.len() + self.failed_players.len();
+        self.sources.extend(self.failed_sources.drain(..));
+        self.players.extend(self.failed_players.drain(..));
+        failed
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrawlConfig {
+    pub platform: ServerRegion,
+    /// Region of match-v5
+    pub routing: RoutingRegion,
+    pub api_key: String,
+    pub queue: RankedQueue,
+    /// Most players to sample per tier, tiers without a quota are crawled whole. A quota is
+    /// split between the divisions of the tier.
+    pub players_per_tier: HashMap<RankedTier, usize>,
+    /// Match IDs requested per player, at most 100
+    pub matches_per_player: i64,
+    /// Only sample matches of this match-v5 queue, e.g. 420 for ranked solo
+    pub match_queue: Option<i64>,
+    /// Only sample matches started after these epoch seconds
+    pub start_time: Option<u64>,
+    /// Players sampled concurrently per step
+    pub fan_out: usize,
+    /// Attempts per request while rate limited
+    pub retries: usize,
+    pub rate_limits: Vec<RateLimit>,
+}
+
+impl CrawlConfig {
+    /// Samples 20 ranked solo matches of every ranked solo player, with development key limits.
+    pub fn new(platform: ServerRegion, api_key: String) -> Self {
+        Self {
+            platform,
+            routing: RoutingRegion::from_server(&platform),
+            api_key,
+            queue: RankedQueue::RankedSolo5x5,
+            players_per_tier: HashMap::new(),
+            matches_per_player: 20,
+            match_queue: Some(420),
+            start_time: None,
+            fan_out: 4,
+            retries: 3,
+            rate_limits: RateLimit::DEVELOPMENT.to_vec(),
+        }
+    }
+}
+
+/// Outcome of a single `LadderCrawler::step`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CrawlStep {
+    /// Source read by the step, None if it sampled players
+    pub source: Option<LadderSource>,
+    /// Whether the source could not be read and was moved to `CrawlFrontier::failed_sources`
+    pub source_failed: bool,
+    pub players_queued: usize,
+    pub players_sampled: usize,
+    /// Match IDs not returned by any earlier step
+    pub new_match_ids: Vec<String>,
+    /// Summoner IDs of players whose PUUID or matches could not be requested, moved to
+    /// `CrawlFrontier::failed_players`
+    pub failed_players: Vec<String>,
+}
+
+pub struct LadderCrawler {
+    pub config: CrawlConfig,
+    pub frontier: CrawlFrontier,
+    limiter: RateLimiter,
+}
+
+impl LadderCrawler {
+    /// Starts a crawl of `tiers`.
+    pub fn new(config: CrawlConfig, tiers: &[RankedTier]) -> Self {
+        Self::resume(config, CrawlFrontier::new(tiers))
+    }
+
+    /// Continues a crawl from a saved frontier.
+    pub fn resume(config: CrawlConfig, frontier: CrawlFrontier) -> Self {
+        let limiter = RateLimiter::new(config.rate_limits.clone());
+        Self {
+            config,
+            frontier,
+            limiter,
+        }
+    }
+
+    /// Samples the next `fan_out` players, or reads the next source if no players are queued.
+    ///
+    /// Returns None once the crawl is done, call `CrawlFrontier::retry_failed` to go on with
+    /// the failed sources and players. The frontier is consistent between steps, so it can be
+    /// saved after any of them.
+    pub async fn step(&mut self) -> Option<CrawlStep> {
+        if !self.frontier.players.is_empty() {
+            return Some(self.sample_players().await);
+        }
+        let source = self.frontier.sources.pop_front()?;
+        Some(self.read_source(source).await)
+    }
+
+    /// Steps until the crawl is done or at least `limit` new match IDs were found.
+    pub async fn run(&mut self, limit: Option<usize>) -> Vec<String> {
+        let mut match_ids = Vec::new();
+        while limit.is_none_or(|limit| match_ids.len() < limit) {
+            let Some(step) = self.step().await else {
+                break;
+            };
+            match_ids.extend(step.new_match_ids);
+        }
+        match_ids
+    }
+
+    async fn read_source(&mut self, source: LadderSource) -> CrawlStep {
+        let tier = source.tier();
+        let entries = match &source {
+            LadderSource::Apex(tier) => {
+                let (platform, api_key, queue) = (
+                    self.config.platform,
+                    &self.config.api_key,
+                    self.config.queue,
+                );
+                self.with_retries(|| LeagueList::apex_by_queue(&platform, api_key, *tier, queue))
+                    .await
+                    .map(LeagueList::into_entries)
+            }
+            LadderSource::Entries {
+                tier,
+                division,
+                page,
+            } => {
+                let (platform, api_key, queue) = (
+                    self.config.platform,
+                    &self.config.api_key,
+                    self.config.queue,
+                );
+                self.with_retries(|| {
+                    LeagueEntry::entries(&platform, api_key, *division, *tier, queue, Some(*page))
+                })
+                .await
+            }
+        };
+        let mut step = CrawlStep::default();
+        let Some(entries) = entries else {
+            error!("Could not read {:?}, keeping it to retry", source);
+            self.frontier.failed_sources.push(source.clone());
+            step.source = Some(source);
+            step.source_failed = true;
+            return step;
+        };
+
+        let quota = self.config.players_per_tier.get(&tier).copied();
+        let queued = self.frontier.queued.entry(tier).or_default();
+        // What is left of the quota is shared with the other sources of the tier, so the
+        // first division doesn't fill it alone
+        let share = quota.map(|quota| {
+            let sources = self.frontier.sources.iter();
+            let sources = 1 + sources.filter(|source| source.tier() == tier).count();
+            quota.saturating_sub(*queued).div_ceil(sources)
+        });
+        let mut page_read = true;
+        for entry in &entries {
+            if share.is_some_and(|share| step.players_queued >= share) {
+                page_read = false;
+                break;
+            }
+            if !self
+                .frontier
+                .seen_players
+                .insert(entry.get_summoner_id().clone())
+            {
+                continue;
+            }
+            self.frontier.players.push_back(LadderPlayer {
+                tier,
+                summoner_id: entry.get_summoner_id().clone(),
+                puuid: entry.get_puuid().clone(),
+            });
+            *queued += 1;
+            step.players_queued += 1;
+        }
+        let quota_reached = quota.is_some_and(|quota| *queued >= quota);
+        if quota_reached {
+            self.frontier.sources.retain(|source| source.tier() != tier);
+        } else if let LadderSource::Entries {
+            tier,
+            division,
+            page,
+        } = &source
+        {
+            // Pages are read in turns with the other divisions. A page left early is read
+            // again, its queued players are skipped as seen.
+            if !entries.is_empty() {
+                self.frontier.sources.push_back(LadderSource::Entries {
+                    tier: *tier,
+                    division: *division,
+                    page: if page_read { page + 1 } else { *page },
+                });
+            }
+        }
+        info!("Read {:?}, queued {} players", source, step.players_queued);
+        step.source = Some(source);
+        step
+    }
+
+    async fn sample_players(&mut self) -> CrawlStep {
+        let count = self.config.fan_out.max(1).min(self.frontier.players.len());
+        let players: Vec<LadderPlayer> = self.frontier.players.drain(..count).collect();
+        let samples = join_all(players.iter().map(|player| self.sample(player))).await;
+
+        let mut step = CrawlStep {
+            players_sampled: players.len(),
+            ..Default::default()
+        };
+        for (player, sample) in players.into_iter().zip(samples) {
+            match sample {
+                Some(match_ids) => {
+                    for match_id in match_ids {
+                        if self.frontier.seen_matches.insert(match_id.clone()) {
+                            step.new_match_ids.push(match_id);
+                        }
+                    }
+                }
+                None => {
+                    step.failed_players.push(player.summoner_id.clone());
+                    self.frontier.failed_players.push(player);
+                }
+            }
+        }
+        step
+    }
+
+    async fn sample(&self, player: &LadderPlayer) -> Option<Vec<String>> {
+        let config = &self.config;
+        let puuid = match &player.puuid {
+            Some(puuid) => puuid.clone(),
+            None => self
+                .with_retries(|| {
+                    Summoner::by_summoner_id(&config.platform, &config.api_key, &player.summoner_id)
+                })
+                .await?
+                .get_puuid()
+                .clone(),
+        };
+        self.with_retries(|| {
+            Match::by_puuid(
+                &config.routing,
+                &config.api_key,
+                &puuid,
+                config.start_time,
+                None,
+                config.match_queue,
+                None,
+                None,
+                Some(config.matches_per_player),
+            )
+        })
+        .await
+    }
+
+    /// Sends a request within the rate limits, retrying while the API responds with 429.
+    async fn with_retries<T, F, Fut>(&self, request: F) -> Option<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Option<T>>,
+    {
+        for _ in 0..=self.config.retries {
+            self.limiter.acquire().await;
+            if let Some(response) = request().await {
+                return Some(response);
+            }
+            let rate_limited_until = cache::rate_limited_until().map(Instant::from_std);
+            if rate_limited_until.is_none_or(|until| until <= Instant::now()) {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::cache::{cache_key, Cache, Endpoint, LruCache, ResponseCache};
+
+    #[tokio::test(start_paused = true)]
+    async fn limiter_spaces_requests() {
+        let limiter = RateLimiter::new(vec![
+            RateLimit {
+                requests: 2,
+                per: Duration::from_secs(1),
+            },
+            RateLimit {
+                requests: 3,
+                per: Duration::from_secs(10),
+            },
+        ]);
+        let start = Instant::now();
+        let mut sent = Vec::new();
+        for _ in 0..6 {
+            limiter.acquire().await;
+            sent.push(start.elapsed().as_secs());
+        }
+        assert_eq!(sent, vec![0, 0, 1, 10, 10, 11]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limiter_without_limits_never_waits() {
+        let limiter = RateLimiter::new(vec![]);
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    /// Serves league entry pages and match IDs from a scoped cache.
+    struct Ladder {
+        backend: Arc<LruCache>,
+        cache: Cache,
+    }
+
+    impl Ladder {
+        fn new() -> Self {
+            let backend = Arc::new(LruCache::new(1000));
+            let cache = Cache::new();
+            cache.set_backend(backend.clone());
+            Self { backend, cache }
+        }
+
+        fn serve(&self, endpoint: Endpoint, url: String, body: String) {
+            self.backend.insert(cache_key(endpoint, &url), body, None);
+        }
+
+        /// Serves a first page of `players` GOLD entries, or a body that fails to parse.
+        fn serve_page(&self, division: RankedDivision, players: usize, valid: bool) {
+            let entries: Vec<serde_json::Value> = (0..players)
+                .map(|n| {
+                    json!({
+                        "leagueId": "league",
+                        "summonerId": format!("{}-{}", division, n),
+                        "puuid": format!("{}-{}", division, n),
+                        "queueType": "RANKED_SOLO_5x5",
+                        "tier": "GOLD",
+                        "rank": division.to_string(),
+                        "leaguePoints": 0,
+                        "wins": 10,
+                        "losses": 10,
+                        "hotStreak": false,
+                        "veteran": false,
+                        "freshBlood": false,
+                        "inactive": false,
+                    })
+                })
+                .collect();
+            let url = format!(
+                "https://euw1.api.riotgames.com/lol/league/v4/entries/RANKED_SOLO_5X5/GOLD/{}?page=1",
+                division
+            );
+            let body = if valid {
+                json!(entries).to_string()
+            } else {
+                "{}".to_string()
+            };
+            self.serve(Endpoint::League, url, body);
+        }
+
+        /// Serves a single match ID of the player, or a body that fails to parse.
+        fn serve_match_ids(&self, puuid: &str, valid: bool) {
+            let url = format!(
+                "https://europe.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids?start=0&queue=420&count=20",
+                puuid
+            );
+            let body = if valid {
+                json!([format!("EUW1_{}", puuid)]).to_string()
+            } else {
+                "{}".to_string()
+            };
+            self.serve(Endpoint::MatchIds, url, body);
+        }
+    }
+
+    #[tokio::test]
+    async fn quotas_are_shared_and_failures_kept() {
+        let ladder = Ladder::new();
+        for division in RankedDivision::ALL {
+            ladder.serve_page(division, 5, division != RankedDivision::IV);
+            for n in 0..5 {
+                let puuid = format!("{}-{}", division, n);
+                ladder.serve_match_ids(&puuid, puuid != "II-0");
+            }
+        }
+        let mut config = CrawlConfig::new(ServerRegion::EUW1, "key".to_string());
+        config.players_per_tier.insert(RankedTier::GOLD, 8);
+        config.rate_limits.clear();
+        let mut crawler = LadderCrawler::new(config, &[RankedTier::GOLD]);
+
+        let match_ids = ladder.cache.scope(crawler.run(None)).await;
+        assert_eq!(match_ids.len(), 7);
+        let frontier = &crawler.frontier;
+        assert!(frontier.is_done());
+        assert_eq!(frontier.queued[&RankedTier::GOLD], 8);
+        let queued = |division: &str| {
+            let prefix = format!("{}-", division);
+            let players = frontier.seen_players.iter();
+            players.filter(|id| id.starts_with(&prefix)).count()
+        };
+        // Divisions take turns, instead of division I filling the quota
+        assert_eq!(
+            [queued("I"), queued("II"), queued("III"), queued("IV")],
+            [3, 3, 2, 0]
+        );
+        assert_eq!(
+            frontier.failed_sources,
+            vec![LadderSource::Entries {
+                tier: RankedTier::GOLD,
+                division: RankedDivision::IV,
+                page: 1,
+            }]
+        );
+        let failed: Vec<&str> = frontier
+            .failed_players
+            .iter()
+            .map(|player| player.summoner_id.as_str())
+            .collect();
+        assert_eq!(failed, vec!["II-0"]);
+
+        // Failures are requeued. The quota is already reached, so IV queues no one
+        ladder.serve_page(RankedDivision::IV, 5, true);
+        ladder.serve_match_ids("II-0", true);
+        assert_eq!(crawler.frontier.retry_failed(), 2);
+        let match_ids = ladder.cache.scope(crawler.run(None)).await;
+        assert_eq!(match_ids, vec!["EUW1_II-0"]);
+        let frontier = &crawler.frontier;
+        assert!(frontier.failed_sources.is_empty() && frontier.failed_players.is_empty());
+        assert_eq!(frontier.seen_players.len(), 8);
+    }
+}
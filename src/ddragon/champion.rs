@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::champion::ChampionId;
+
+use super::{DataFile, Image, StaticEntry, StaticIndex};
+
+pub type ChampionIndex = StaticIndex<ChampionId, Champion>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Champion {
+    /// Internal name, e.g. "MonkeyKing"
+    pub id: String,
+    /// Numeric champion ID as a string, e.g. "62"
+    pub key: String,
+    /// Display name, e.g. "Wukong"
+    pub name: String,
+    pub title: String,
+    pub blurb: String,
+    pub info: ChampionInfo,
+    pub image: Image,
+    pub tags: Vec<String>,
+    /// Resource type, e.g. "Mana" or "Energy"
+    pub partype: String,
+    pub stats: ChampionStats,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChampionInfo {
+    pub attack: i64,
+    pub defense: i64,
+    pub magic: i64,
+    pub difficulty: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChampionStats {
+    pub hp: f64,
+    pub hpperlevel: f64,
+    pub mp: f64,
+    pub mpperlevel: f64,
+    pub movespeed: f64,
+    pub armor: f64,
+    pub armorperlevel: f64,
+    pub spellblock: f64,
+    pub spellblockperlevel: f64,
+    pub attackrange: f64,
+    pub hpregen: f64,
+    pub hpregenperlevel: f64,
+    pub mpregen: f64,
+    pub mpregenperlevel: f64,
+    pub crit: f64,
+    pub critperlevel: f64,
+    pub attackdamage: f64,
+    pub attackdamageperlevel: f64,
+    pub attackspeedperlevel: f64,
+    pub attackspeed: f64,
+}
+
+impl StaticEntry for Champion {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.image.path())
+    }
+}
+
+impl StaticIndex<ChampionId, Champion> {
+    /// Finds a champion by its internal name as used in `Participant.champion_name`.
+    pub fn by_internal_name(&self, id: &str) -> Option<&Champion> {
+        self.entries.values().find(|champion| champion.id == id)
+    }
+}
+
+pub(crate) fn index(file: DataFile<Champion>) -> ChampionIndex {
+    let mut entries = HashMap::with_capacity(file.data.len());
+    for champion in file.data.into_values() {
+        match champion.key.parse() {
+            Ok(key) => {
+                entries.insert(ChampionId(key), champion);
+            }
+            Err(e) => error!("{e:?}, invalid key for champion '{}'", champion.id),
+        }
+    }
+    StaticIndex {
+        version: file.version,
+        entries,
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use super::{DataFile, Image, StaticEntry, StaticIndex};
+
+pub type ItemIndex = StaticIndex<i64, Item>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Item {
+    pub name: String,
+    /// HTML-like description including stats and passives
+    pub description: String,
+    pub plaintext: String,
+    /// IDs of the items this builds into, as strings
+    #[serde(default)]
+    pub into: Vec<String>,
+    /// IDs of the components of this item, as strings
+    #[serde(default)]
+    pub from: Vec<String>,
+    pub image: Image,
+    pub gold: ItemGold,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Map ID, as a string, to whether the item is available there
+    #[serde(default)]
+    pub maps: HashMap<String, bool>,
+    /// Stat name, e.g. "FlatPhysicalDamageMod", to value
+    #[serde(default)]
+    pub stats: HashMap<String, f64>,
+    pub depth: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ItemGold {
+    pub base: i64,
+    pub purchasable: bool,
+    pub total: i64,
+    pub sell: i64,
+}
+
+impl StaticEntry for Item {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.image.path())
+    }
+}
+
+pub(crate) fn index(file: DataFile<Item>) -> ItemIndex {
+    let mut entries = HashMap::with_capacity(file.data.len());
+    for (id, item) in file.data {
+        match id.parse() {
+            Ok(id) => {
+                entries.insert(id, item);
+            }
+            Err(e) => error!("{e:?}, invalid item id '{}'", id),
+        }
+    }
+    StaticIndex {
+        version: file.version,
+        entries,
+    }
+}
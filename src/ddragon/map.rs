@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use super::{DataFile, Image, StaticEntry, StaticIndex};
+
+pub type MapIndex = StaticIndex<i64, Map>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Map {
+    #[serde(rename = "MapName")]
+    pub map_name: String,
+    /// Numeric map ID as a string, matching `Info.map_id`
+    #[serde(rename = "MapId")]
+    pub map_id: String,
+    pub image: Image,
+}
+
+impl StaticEntry for Map {
+    fn name(&self) -> &str {
+        &self.map_name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.image.path())
+    }
+}
+
+pub(crate) fn index(file: DataFile<Map>) -> MapIndex {
+    let mut entries = HashMap::with_capacity(file.data.len());
+    for map in file.data.into_values() {
+        match map.map_id.parse() {
+            Ok(id) => {
+                entries.insert(id, map);
+            }
+            Err(e) => error!("{e:?}, invalid id for map '{}'", map.map_name),
+        }
+    }
+    StaticIndex {
+        version: file.version,
+        entries,
+    }
+}
@@ -0,0 +1,426 @@
+//! Client for Data Dragon, Riot's static data CDN, resolving the numeric IDs found in match
+//! data to names, icons and stats.
+
+pub mod champion;
+pub mod item;
+pub mod map;
+pub mod offline;
+pub mod profile_icon;
+pub mod queue;
+pub mod rune;
+pub mod rune_page;
+pub mod summoner_spell;
+
+use std::{collections::HashMap, hash::Hash, path::Path};
+
+use log::error;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::patch::Patch;
+
+use self::{
+    champion::ChampionIndex, item::ItemIndex, map::MapIndex, profile_icon::ProfileIconIndex,
+    queue::QueueIndex, rune::RuneIndex, summoner_spell::SummonerSpellIndex,
+};
+
+pub const DEFAULT_HOST: &str = "https://ddragon.leagueoflegends.com";
+/// Host of queues.json, which is not part of Data Dragon
+pub const DEFAULT_DOCS_HOST: &str = "https://static.developer.riotgames.com";
+
+/// Static data entry with a display name and an icon.
+pub trait StaticEntry {
+    fn name(&self) -> &str;
+    /// Icon path relative to the source's image root, None if the entry has no icon
+    fn icon_path(&self) -> Option<String>;
+}
+
+/// Lookup of static data entries by ID, shared by every static data source.
+pub trait Lookup<K> {
+    type Entry: StaticEntry;
+
+    fn lookup(&self, id: K) -> Option<&Self::Entry>;
+
+    fn name_of<'a>(&'a self, id: K) -> Option<&'a str>
+    where
+        Self::Entry: 'a,
+    {
+        self.lookup(id).map(|entry| entry.name())
+    }
+}
+
+/// Entries of a single static data file, keyed by their numeric ID.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StaticIndex<K: Eq + Hash, V> {
+    /// Data Dragon version the data was loaded from, e.g. "14.23.1"
+    pub version: String,
+    pub entries: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V: StaticEntry> Lookup<K> for StaticIndex<K, V> {
+    type Entry = V;
+
+    fn lookup(&self, id: K) -> Option<&V> {
+        self.entries.get(&id)
+    }
+}
+
+impl<K: Eq + Hash, V: StaticEntry> StaticIndex<K, V> {
+    /// Finds an entry by its display name, case insensitive.
+    pub fn by_name(&self, name: &str) -> Option<&V> {
+        self.entries
+            .values()
+            .find(|entry| entry.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Image {
+    pub full: String,
+    pub sprite: String,
+    /// Image directory, e.g. "champion" or "item"
+    pub group: String,
+    pub x: i64,
+    pub y: i64,
+    pub w: i64,
+    pub h: i64,
+}
+
+impl Image {
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.group, self.full)
+    }
+}
+
+/// Every static data file of a single version and locale.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StaticData {
+    pub version: String,
+    pub locale: String,
+    pub champions: ChampionIndex,
+    pub items: ItemIndex,
+    pub runes: RuneIndex,
+    pub summoner_spells: SummonerSpellIndex,
+    pub profile_icons: ProfileIconIndex,
+    pub maps: MapIndex,
+    pub queues: QueueIndex,
+}
+
+/// Layout shared by every `{locale}/*.json` file except runesReforged.json.
+#[derive(Deserialize)]
+pub(crate) struct DataFile<T> {
+    pub version: String,
+    pub data: HashMap<String, T>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DataDragon {
+    /// Base URL such as `DEFAULT_HOST` or a stub server, or a `file://` path to a directory
+    /// laid out like the CDN (`api/versions.json`, `cdn/{version}/data/{locale}/...`)
+    pub host: String,
+    /// Base URL or `file://` path serving `docs/lol/queues.json`
+    pub docs_host: String,
+}
+
+impl Default for DataDragon {
+    fn default() -> Self {
+        Self {
+            host: String::from(DEFAULT_HOST),
+            docs_host: String::from(DEFAULT_DOCS_HOST),
+        }
+    }
+}
+
+impl DataDragon {
+    /// Uses `host` for both Data Dragon and queues.json.
+    pub fn new(host: impl Into<String>) -> Self {
+        let host = host.into().trim_end_matches('/').to_string();
+        Self {
+            docs_host: host.clone(),
+            host,
+        }
+    }
+
+    /// Requests every released version, newest first.
+    pub async fn versions(&self) -> Option<Vec<String>> {
+        self.fetch("api/versions.json").await
+    }
+
+    pub async fn latest_version(&self) -> Option<String> {
+        self.versions().await?.into_iter().next()
+    }
+
+    pub async fn champions(&self, version: &str, locale: &str) -> Option<ChampionIndex> {
+        let file = self
+            .fetch(&data_path(version, locale, "champion.json"))
+            .await?;
+        Some(champion::index(file))
+    }
+
+    pub async fn items(&self, version: &str, locale: &str) -> Option<ItemIndex> {
+        let file = self.fetch(&data_path(version, locale, "item.json")).await?;
+        Some(item::index(file))
+    }
+
+    pub async fn runes(&self, version: &str, locale: &str) -> Option<RuneIndex> {
+        let trees = self
+            .fetch(&data_path(version, locale, "runesReforged.json"))
+            .await?;
+        Some(RuneIndex::new(version.to_string(), trees))
+    }
+
+    pub async fn summoner_spells(&self, version: &str, locale: &str) -> Option<SummonerSpellIndex> {
+        let file = self
+            .fetch(&data_path(version, locale, "summoner.json"))
+            .await?;
+        Some(summoner_spell::index(file))
+    }
+
+    pub async fn profile_icons(&self, version: &str, locale: &str) -> Option<ProfileIconIndex> {
+        let file = self
+            .fetch(&data_path(version, locale, "profileicon.json"))
+            .await?;
+        Some(profile_icon::index(file))
+    }
+
+    pub async fn maps(&self, version: &str, locale: &str) -> Option<MapIndex> {
+        let file = self.fetch(&data_path(version, locale, "map.json")).await?;
+        Some(map::index(file))
+    }
+
+    /// Requests queues.json, which is not versioned, so the index takes the given version.
+    pub async fn queues(&self, version: &str) -> Option<QueueIndex> {
+        let queues = fetch(&self.docs_host, "docs/lol/queues.json").await?;
+        Some(queue::index(version.to_string(), queues))
+    }
+
+    /// Requests every static data file of a version, e.g. "14.23.1", and locale, e.g. "en_US".
+    pub async fn static_data(&self, version: &str, locale: &str) -> Option<StaticData> {
+        Some(StaticData {
+            version: version.to_string(),
+            locale: locale.to_string(),
+            champions: self.champions(version, locale).await?,
+            items: self.items(version, locale).await?,
+            runes: self.runes(version, locale).await?,
+            summoner_spells: self.summoner_spells(version, locale).await?,
+            profile_icons: self.profile_icons(version, locale).await?,
+            maps: self.maps(version, locale).await?,
+            queues: self.queues(version).await?,
+        })
+    }
+
+    /// Requests the static data of the Data Dragon version matching a patch, falling back to
+    /// the patch's usual version if it is missing from the versions list.
+    pub async fn static_data_for_patch(&self, patch: &Patch, locale: &str) -> Option<StaticData> {
+        let versions = self.versions().await?;
+        let version = match patch.ddragon_version(&versions) {
+            Some(version) => version.clone(),
+            None => patch.default_ddragon_version(),
+        };
+        self.static_data(&version, locale).await
+    }
+
+    /// URL of a champion, item, spell, profile icon or map image.
+    pub fn image_url(&self, version: &str, image: &Image) -> String {
+        format!("{}/cdn/{}/img/{}", self.host, version, image.path())
+    }
+
+    /// URL of a rune or rune tree icon, which are not versioned.
+    pub fn rune_icon_url(&self, icon: &str) -> String {
+        format!("{}/cdn/img/{}", self.host, icon)
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, path: &str) -> Option<T> {
+        fetch(&self.host, path).await
+    }
+}
+
+pub(crate) async fn fetch<T: DeserializeOwned>(host: &str, path: &str) -> Option<T> {
+    let resp = if let Some(directory) = host.strip_prefix("file://") {
+        match std::fs::read_to_string(Path::new(directory).join(path)) {
+            Ok(t) => t,
+            Err(e) => {
+                error!("{e:?}, {}/{}", directory, path);
+                return None;
+            }
+        }
+    } else {
+        let request_url = format!("{}/{}", host, path);
+        let resp = reqwest::get(request_url).await.unwrap();
+        resp.text().await.expect("Could not parse")
+    };
+    parse(path, &resp)
+}
+
+pub(crate) fn parse<T: DeserializeOwned>(name: &str, text: &str) -> Option<T> {
+    match serde_json::from_str(text) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", name);
+            None
+        }
+    }
+}
+
+fn data_path(version: &str, locale: &str, file: &str) -> String {
+    format!("cdn/{}/data/{}/{}", version, locale, file)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::champion::ChampionId;
+
+    const VERSION: &str = "14.23.1";
+
+    fn image() -> Value {
+        json!({"full": "X.png", "sprite": "s.png", "group": "g", "x": 0, "y": 0, "w": 48, "h": 48})
+    }
+
+    fn champion(id: &str, key: &str, name: &str) -> Value {
+        let stats: serde_json::Map<String, Value> = [
+            "hp",
+            "hpperlevel",
+            "mp",
+            "mpperlevel",
+            "movespeed",
+            "armor",
+            "armorperlevel",
+            "spellblock",
+            "spellblockperlevel",
+            "attackrange",
+            "hpregen",
+            "hpregenperlevel",
+            "mpregen",
+            "mpregenperlevel",
+            "crit",
+            "critperlevel",
+            "attackdamage",
+            "attackdamageperlevel",
+            "attackspeedperlevel",
+            "attackspeed",
+        ]
+        .into_iter()
+        .map(|stat| (stat.to_string(), json!(1.0)))
+        .collect();
+        json!({
+            "id": id,
+            "key": key,
+            "name": name,
+            "title": "t",
+            "blurb": "b",
+            "info": {"attack": 1, "defense": 1, "magic": 1, "difficulty": 1},
+            "image": image(),
+            "tags": ["Fighter"],
+            "partype": "Mana",
+            "stats": stats,
+        })
+    }
+
+    fn write(root: &Path, path: &str, value: Value) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value.to_string()).unwrap();
+    }
+
+    fn data_dragon() -> (tempfile::TempDir, DataDragon) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "api/versions.json", json!([VERSION, "14.22.1"]));
+        write(
+            root,
+            &data_path(VERSION, "en_US", "champion.json"),
+            json!({
+                "version": VERSION,
+                "data": {
+                    "MonkeyKing": champion("MonkeyKing", "62", "Wukong"),
+                    "Aatrox": champion("Aatrox", "266", "Aatrox"),
+                    "Broken": champion("Broken", "not a key", "Broken"),
+                },
+            }),
+        );
+        write(
+            root,
+            &data_path(VERSION, "en_US", "item.json"),
+            json!({
+                "version": VERSION,
+                "data": {
+                    "1001": {
+                        "name": "Boots",
+                        "description": "d",
+                        "plaintext": "p",
+                        "into": ["3006"],
+                        "image": image(),
+                        "gold": {"base": 300, "purchasable": true, "total": 300, "sell": 210},
+                    },
+                },
+            }),
+        );
+        write(
+            root,
+            &data_path(VERSION, "en_US", "summoner.json"),
+            json!({
+                "version": VERSION,
+                "data": {
+                    "SummonerFlash": {
+                        "id": "SummonerFlash",
+                        "key": "4",
+                        "name": "Flash",
+                        "description": "d",
+                        "cooldown": [300.0],
+                        "summonerLevel": 7,
+                        "image": image(),
+                    },
+                },
+            }),
+        );
+        let data_dragon = DataDragon::new(format!("file://{}/", root.display()));
+        (dir, data_dragon)
+    }
+
+    #[tokio::test]
+    async fn reads_versions_from_a_local_directory() {
+        let (_dir, data_dragon) = data_dragon();
+        assert_eq!(
+            data_dragon.versions().await,
+            Some(vec![VERSION.to_string(), "14.22.1".to_string()])
+        );
+        assert_eq!(data_dragon.latest_version().await.as_deref(), Some(VERSION));
+        assert!(data_dragon.champions("14.22.1", "en_US").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn indexes_champions_by_key() {
+        let (_dir, data_dragon) = data_dragon();
+        let champions = data_dragon.champions(VERSION, "en_US").await.unwrap();
+        assert_eq!(champions.version, VERSION);
+        assert_eq!(champions.entries.len(), 2);
+        assert_eq!(champions.name_of(ChampionId(62)), Some("Wukong"));
+        assert_eq!(champions.name_of(ChampionId(266)), Some("Aatrox"));
+        let wukong = champions.by_internal_name("MonkeyKing").unwrap();
+        assert_eq!(wukong.name, "Wukong");
+        assert!(champions.by_internal_name("Wukong").is_none());
+        assert!(champions.by_internal_name("Broken").is_none());
+        assert_eq!(champions.by_name("wukong").unwrap().id, "MonkeyKing");
+    }
+
+    #[tokio::test]
+    async fn indexes_items_and_summoner_spells_by_id() {
+        let (_dir, data_dragon) = data_dragon();
+        let items = data_dragon.items(VERSION, "en_US").await.unwrap();
+        let boots = items.lookup(1001).unwrap();
+        assert_eq!(boots.name, "Boots");
+        assert_eq!(boots.into, vec!["3006"]);
+        assert!(boots.from.is_empty());
+        assert_eq!(boots.gold.total, 300);
+
+        let spells = data_dragon.summoner_spells(VERSION, "en_US").await.unwrap();
+        assert_eq!(spells.version, VERSION);
+        assert_eq!(spells.name_of(4), Some("Flash"));
+        assert_eq!(spells.lookup(4).unwrap().id, "SummonerFlash");
+        assert!(spells.lookup(12).is_none());
+    }
+}
@@ -0,0 +1,290 @@
+//! Loading static data from disk or from data compiled into the binary, for environments
+//! without network access.
+//!
+//! An extracted Data Dragon tarball (dragontail-{version}.tgz) is laid out as
+//! `{root}/{version}/data/{locale}/*.json`. queues.json is not part of the tarball and is read
+//! from `{root}/queues.json` if present.
+//!
+//! With the `bundled-data` feature, `StaticData::bundled` embeds the files found in the
+//! directory named by the `LEAGUE_API_STATIC_DATA_DIR` environment variable at build time,
+//! which must contain the data files of a single version and locale as well as queues.json.
+//! Relative paths are resolved against this crate's root. The build fails if files are
+//! missing from the directory, and `bundled` returns None if the variable was not set.
+
+use std::path::Path;
+
+use log::{error, warn};
+
+use super::{
+    champion, item, map, parse, profile_icon, queue, rune::RuneIndex, summoner_spell, StaticData,
+};
+use crate::patch::Patch;
+
+impl StaticData {
+    /// Loads a locale from an extracted Data Dragon tarball, using the newest version found
+    /// in `root` if `version` is None.
+    pub fn from_directory(
+        root: impl AsRef<Path>,
+        version: Option<&str>,
+        locale: &str,
+    ) -> Option<Self> {
+        let root = root.as_ref();
+        let version = match version {
+            Some(version) => version.to_string(),
+            None => directory_versions(root).into_iter().next().or_else(|| {
+                error!("No Data Dragon versions found in {}", root.display());
+                None
+            })?,
+        };
+        let data_dir = root.join(&version).join("data").join(locale);
+        let queues = std::fs::read_to_string(root.join("queues.json")).ok();
+        Self::from_files(
+            locale,
+            |file| match std::fs::read_to_string(data_dir.join(file)) {
+                Ok(t) => Some(t),
+                Err(e) => {
+                    error!("{e:?}, {}", data_dir.join(file).display());
+                    None
+                }
+            },
+            queues,
+        )
+    }
+
+    /// Loads the newest version of a patch from an extracted Data Dragon tarball.
+    pub fn from_directory_for_patch(
+        root: impl AsRef<Path>,
+        patch: &Patch,
+        locale: &str,
+    ) -> Option<Self> {
+        let root = root.as_ref();
+        let versions = directory_versions(root);
+        let Some(version) = patch.ddragon_version(&versions) else {
+            error!(
+                "No Data Dragon version for patch {} in {}",
+                patch,
+                root.display()
+            );
+            return None;
+        };
+        Self::from_directory(root, Some(version), locale)
+    }
+
+    /// Loads the data embedded at build time, see the module documentation.
+    #[cfg(all(feature = "bundled-data", bundled_static_data))]
+    pub fn bundled() -> Option<Self> {
+        macro_rules! bundled {
+            ($file:literal) => {
+                include_str!(concat!(env!("LEAGUE_API_BUNDLED_DATA_DIR"), "/", $file))
+            };
+        }
+        let locale = option_env!("LEAGUE_API_STATIC_DATA_LOCALE").unwrap_or("en_US");
+        Self::from_files(
+            locale,
+            |file| {
+                Some(String::from(match file {
+                    "champion.json" => bundled!("champion.json"),
+                    "item.json" => bundled!("item.json"),
+                    "runesReforged.json" => bundled!("runesReforged.json"),
+                    "summoner.json" => bundled!("summoner.json"),
+                    "profileicon.json" => bundled!("profileicon.json"),
+                    "map.json" => bundled!("map.json"),
+                    _ => return None,
+                }))
+            },
+            Some(String::from(bundled!("queues.json"))),
+        )
+    }
+
+    /// Loads the data embedded at build time, see the module documentation.
+    #[cfg(all(feature = "bundled-data", not(bundled_static_data)))]
+    pub fn bundled() -> Option<Self> {
+        error!("No static data was bundled, LEAGUE_API_STATIC_DATA_DIR was not set at build time");
+        None
+    }
+
+    /// Builds the indexes from file contents, taking the version from champion.json.
+    fn from_files(
+        locale: &str,
+        read: impl Fn(&str) -> Option<String>,
+        queues: Option<String>,
+    ) -> Option<Self> {
+        let champions = champion::index(parse("champion.json", &read("champion.json")?)?);
+        let version = champions.version.clone();
+        let queues = match queues {
+            Some(queues) => parse("queues.json", &queues)?,
+            None => {
+                warn!("No queues.json found, queue lookups will be empty");
+                Vec::new()
+            }
+        };
+        Some(Self {
+            locale: locale.to_string(),
+            champions,
+            items: item::index(parse("item.json", &read("item.json")?)?),
+            runes: RuneIndex::new(
+                version.clone(),
+                parse("runesReforged.json", &read("runesReforged.json")?)?,
+            ),
+            summoner_spells: summoner_spell::index(parse(
+                "summoner.json",
+                &read("summoner.json")?,
+            )?),
+            profile_icons: profile_icon::index(parse(
+                "profileicon.json",
+                &read("profileicon.json")?,
+            )?),
+            maps: map::index(parse("map.json", &read("map.json")?)?),
+            queues: queue::index(version.clone(), queues),
+            version,
+        })
+    }
+}
+
+/// Versions present in an extracted Data Dragon tarball, newest first.
+pub fn directory_versions(root: impl AsRef<Path>) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(root.as_ref()) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<(Vec<u64>, String)> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| Some((parse_version(&name)?, name)))
+        .collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+    versions.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Splits a version such as "14.23.1" into its numeric parts, None if it is not a version.
+pub(crate) fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::ddragon::Lookup;
+
+    const CHAMPION: &str = r#"{"type":"champion","format":"standAloneComplex","version":"{version}","data":{"MonkeyKing":{"version":"{version}","id":"MonkeyKing","key":"62","name":"Wukong","title":"the Monkey King","blurb":"b","info":{"attack":8,"defense":5,"magic":2,"difficulty":3},"image":{"full":"X.png","sprite":"s.png","group":"champion","x":0,"y":0,"w":48,"h":48},"tags":["Fighter"],"partype":"Mana","stats":{"hp":610,"hpperlevel":99,"mp":330,"mpperlevel":65,"movespeed":340,"armor":31,"armorperlevel":4.7,"spellblock":28,"spellblockperlevel":2.05,"attackrange":175,"hpregen":3.5,"hpregenperlevel":0.65,"mpregen":8,"mpregenperlevel":0.65,"crit":0,"critperlevel":0,"attackdamage":66,"attackdamageperlevel":3.5,"attackspeedperlevel":3,"attackspeed":0.69}}}}"#;
+    const ITEM: &str = r#"{"type":"item","version":"{version}","basic":{},"data":{"1001":{"name":"Boots","description":"d","colloq":";","plaintext":"p","into":["3006"],"image":{"full":"X.png","sprite":"s.png","group":"champion","x":0,"y":0,"w":48,"h":48},"gold":{"base":300,"purchasable":true,"total":300,"sell":210},"tags":["Boots"],"maps":{"11":true},"stats":{"FlatMovementSpeedMod":25}}},"groups":[],"tree":[]}"#;
+    const RUNES: &str = r#"[{"id":8100,"key":"Domination","icon":"perk-images/Styles/7200_Domination.png","name":"Domination","slots":[{"runes":[{"id":8112,"key":"Electrocute","icon":"i.png","name":"Electrocute","shortDesc":"s","longDesc":"l"}]},{"runes":[{"id":8126,"key":"CheapShot","icon":"i.png","name":"Cheap Shot","shortDesc":"s","longDesc":"l"}]}]}]"#;
+    const SUMMONER: &str = r#"{"type":"summoner","version":"{version}","data":{"SummonerFlash":{"id":"SummonerFlash","name":"Flash","description":"d","tooltip":"t","maxrank":1,"cooldown":[300],"cooldownBurn":"300","cost":[0],"costBurn":"0","datavalues":{},"effect":[],"effectBurn":[],"vars":[],"key":"4","summonerLevel":7,"modes":["CLASSIC"],"costType":"&nbsp;","maxammo":"-1","range":[425],"rangeBurn":"425","image":{"full":"X.png","sprite":"s.png","group":"champion","x":0,"y":0,"w":48,"h":48},"resource":"No Cost"}}}"#;
+    const PROFILE_ICON: &str = r#"{"type":"profileicon","version":"{version}","data":{"0":{"id":0,"image":{"full":"X.png","sprite":"s.png","group":"champion","x":0,"y":0,"w":48,"h":48}}}}"#;
+    const MAP: &str = r#"{"type":"map","version":"{version}","data":{"11":{"MapName":"Summoner's Rift","MapId":"11","image":{"full":"X.png","sprite":"s.png","group":"champion","x":0,"y":0,"w":48,"h":48}}}}"#;
+    const QUEUES: &str = r#"[{"queueId":420,"map":"Summoner's Rift","description":"5v5 Ranked Solo games","notes":null},{"queueId":0,"map":"Custom games","description":null,"notes":null}]"#;
+
+    /// Writes a minimal tarball layout for each version, champion.json carries the version.
+    fn tarball(versions: &[&str], queues: bool) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for version in versions {
+            let data_dir = dir.path().join(version).join("data").join("en_US");
+            fs::create_dir_all(&data_dir).unwrap();
+            for (file, text) in [
+                ("champion.json", CHAMPION),
+                ("item.json", ITEM),
+                ("runesReforged.json", RUNES),
+                ("summoner.json", SUMMONER),
+                ("profileicon.json", PROFILE_ICON),
+                ("map.json", MAP),
+            ] {
+                fs::write(data_dir.join(file), text.replace("{version}", version)).unwrap();
+            }
+        }
+        if queues {
+            fs::write(dir.path().join("queues.json"), QUEUES).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn lists_directory_versions_newest_first() {
+        let dir = tarball(&["14.9.1", "14.23.1", "14.10.2", "14.10.10"], true);
+        fs::create_dir(dir.path().join("lolpatch_14.23")).unwrap();
+        fs::create_dir(dir.path().join("img")).unwrap();
+        assert_eq!(
+            directory_versions(dir.path()),
+            vec!["14.23.1", "14.10.10", "14.10.2", "14.9.1"]
+        );
+        assert!(directory_versions(dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn loads_the_newest_or_requested_version() {
+        let dir = tarball(&["14.22.1", "14.23.1"], true);
+        let data = StaticData::from_directory(dir.path(), None, "en_US").unwrap();
+        assert_eq!(data.version, "14.23.1");
+        assert_eq!(data.locale, "en_US");
+        assert_eq!(
+            data.champions.by_internal_name("MonkeyKing").unwrap().name,
+            "Wukong"
+        );
+        assert_eq!(data.items.name_of(1001), Some("Boots"));
+        assert_eq!(data.summoner_spells.name_of(4), Some("Flash"));
+        assert_eq!(data.queues.version, "14.23.1");
+        assert!(data.queues.lookup(420).is_some());
+
+        let data = StaticData::from_directory(dir.path(), Some("14.22.1"), "en_US").unwrap();
+        assert_eq!(data.version, "14.22.1");
+        assert_eq!(data.items.version, "14.22.1");
+        assert!(StaticData::from_directory(dir.path(), Some("14.21.1"), "en_US").is_none());
+        assert!(StaticData::from_directory(dir.path(), None, "ko_KR").is_none());
+    }
+
+    #[test]
+    fn loads_the_newest_version_of_a_patch() {
+        let dir = tarball(&["14.22.1", "14.23.1", "14.23.2", "14.24.1"], true);
+        let patch = Patch {
+            major: 14,
+            minor: 23,
+        };
+        let data = StaticData::from_directory_for_patch(dir.path(), &patch, "en_US").unwrap();
+        assert_eq!(data.version, "14.23.2");
+        let patch = Patch {
+            major: 14,
+            minor: 21,
+        };
+        assert!(StaticData::from_directory_for_patch(dir.path(), &patch, "en_US").is_none());
+    }
+
+    #[test]
+    fn missing_queues_json_leaves_queues_empty() {
+        let dir = tarball(&["14.23.1"], false);
+        let data = StaticData::from_directory(dir.path(), None, "en_US").unwrap();
+        assert!(data.queues.entries.is_empty());
+        assert!(!data.champions.entries.is_empty());
+    }
+
+    #[test]
+    fn empty_directory_has_no_data() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(StaticData::from_directory(dir.path(), None, "en_US").is_none());
+    }
+
+    #[cfg(all(feature = "bundled-data", bundled_static_data))]
+    #[test]
+    fn loads_bundled_index() {
+        let data = StaticData::bundled().expect("bundled data should load");
+        assert!(parse_version(&data.version).is_some());
+        assert!(!data.champions.entries.is_empty());
+        assert!(!data.items.entries.is_empty());
+        assert!(!data.summoner_spells.entries.is_empty());
+        assert!(!data.queues.entries.is_empty());
+
+        let (id, champion) = data.champions.entries.iter().next().unwrap();
+        assert_eq!(data.champions.name_of(*id), Some(champion.name.as_str()));
+        assert_eq!(
+            data.champions.by_internal_name(&champion.id).unwrap().name,
+            champion.name
+        );
+        // Every index is stamped with the version of champion.json
+        assert_eq!(data.items.version, data.version);
+        assert_eq!(data.queues.version, data.version);
+    }
+
+    #[cfg(all(feature = "bundled-data", not(bundled_static_data)))]
+    #[test]
+    fn bundled_is_none_without_data() {
+        assert!(StaticData::bundled().is_none());
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{DataFile, Image, StaticEntry, StaticIndex};
+
+pub type ProfileIconIndex = StaticIndex<i64, ProfileIcon>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileIcon {
+    pub id: i64,
+    pub image: Image,
+}
+
+impl StaticEntry for ProfileIcon {
+    /// Profile icons are unnamed, this is the image file name
+    fn name(&self) -> &str {
+        &self.image.full
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.image.path())
+    }
+}
+
+pub(crate) fn index(file: DataFile<ProfileIcon>) -> ProfileIconIndex {
+    let entries: HashMap<i64, ProfileIcon> = file
+        .data
+        .into_values()
+        .map(|icon| (icon.id, icon))
+        .collect();
+    StaticIndex {
+        version: file.version,
+        entries,
+    }
+}
@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{StaticEntry, StaticIndex};
+
+pub type QueueIndex = StaticIndex<i64, Queue>;
+
+/// Entry of Riot's queues.json, which is published alongside the API docs rather than on
+/// Data Dragon.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Queue {
+    /// Matches `Info.queue_id`
+    pub queue_id: i64,
+    pub map: String,
+    /// e.g. "5v5 Ranked Solo games", None for custom games
+    pub description: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl StaticEntry for Queue {
+    fn name(&self) -> &str {
+        self.description.as_deref().unwrap_or(&self.map)
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        None
+    }
+}
+
+pub(crate) fn index(version: String, queues: Vec<Queue>) -> QueueIndex {
+    let entries: HashMap<i64, Queue> = queues
+        .into_iter()
+        .map(|queue| (queue.queue_id, queue))
+        .collect();
+    StaticIndex { version, entries }
+}
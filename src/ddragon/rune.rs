@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Lookup, StaticEntry};
+
+/// A rune path such as Precision, matching `PerkStyle.style`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuneTree {
+    pub id: i64,
+    pub key: String,
+    pub icon: String,
+    pub name: String,
+    /// The keystone slot first, followed by the three minor rune slots
+    pub slots: Vec<RuneSlot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuneSlot {
+    pub runes: Vec<Rune>,
+}
+
+/// A single rune, matching `PerkStyleSelection.perk`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Rune {
+    pub id: i64,
+    pub key: String,
+    pub icon: String,
+    pub name: String,
+    pub short_desc: String,
+    pub long_desc: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuneIndex {
+    pub version: String,
+    pub trees: Vec<RuneTree>,
+    runes: HashMap<i64, Rune>,
+    /// Rune ID to the ID of the tree it belongs to
+    tree_of: HashMap<i64, i64>,
+}
+
+impl RuneIndex {
+    pub fn new(version: String, trees: Vec<RuneTree>) -> Self {
+        let mut runes = HashMap::new();
+        let mut tree_of = HashMap::new();
+        for tree in &trees {
+            for rune in tree.slots.iter().flat_map(|slot| &slot.runes) {
+                runes.insert(rune.id, rune.clone());
+                tree_of.insert(rune.id, tree.id);
+            }
+        }
+        Self {
+            version,
+            trees,
+            runes,
+            tree_of,
+        }
+    }
+
+    pub fn tree(&self, id: i64) -> Option<&RuneTree> {
+        self.trees.iter().find(|tree| tree.id == id)
+    }
+
+    /// Tree the given rune belongs to.
+    pub fn tree_of(&self, rune_id: i64) -> Option<&RuneTree> {
+        self.tree(*self.tree_of.get(&rune_id)?)
+    }
+
+    /// Whether the rune is in the keystone slot of its tree.
+    pub fn is_keystone(&self, rune_id: i64) -> bool {
+        self.tree_of(rune_id)
+            .and_then(|tree| tree.slots.first())
+            .is_some_and(|slot| slot.runes.iter().any(|rune| rune.id == rune_id))
+    }
+}
+
+impl Lookup<i64> for RuneIndex {
+    type Entry = Rune;
+
+    fn lookup(&self, id: i64) -> Option<&Rune> {
+        self.runes.get(&id)
+    }
+}
+
+impl StaticEntry for Rune {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.icon.clone())
+    }
+}
+
+impl StaticEntry for RuneTree {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.icon.clone())
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::perk::PerkIndex,
+    lol_match::{PerkStyle, PerkStyleSelection, Perks},
+};
+
+use super::{rune::RuneIndex, Lookup, StaticEntry};
+
+/// A decoded `Perks`, with every ID resolved to a name.
+///
+/// Displays as one rune per line, e.g.
+///
+/// ```text
+/// Precision: Conqueror (Total Healing: 1234)
+///   Triumph (Total health restored: 567, Bonus gold granted: 60)
+///   ...
+/// Resolve
+///   Bone Plating (Total damage blocked: 890)
+///   ...
+/// Shards: Adaptive Force, Adaptive Force, Health Scaling
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RunePage {
+    pub primary_tree: RuneTreeName,
+    pub secondary_tree: RuneTreeName,
+    pub keystone: RuneSelection,
+    /// Runes of the primary tree after the keystone, usually three
+    pub primary_runes: Vec<RuneSelection>,
+    /// Runes of the secondary tree, usually two
+    pub secondary_runes: Vec<RuneSelection>,
+    pub stat_shards: StatShards,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuneTreeName {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuneSelection {
+    pub id: i64,
+    pub name: String,
+    pub vars: [i64; 3],
+    /// The vars filled into their labels, e.g. "Total Damage Dealt: 1234". Raw
+    /// "var1: 1234" pairs of the non-zero vars if no labels were given.
+    pub stats: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatShards {
+    pub offense: StatShard,
+    pub flex: StatShard,
+    pub defense: StatShard,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatShard {
+    pub id: i64,
+    pub name: String,
+}
+
+impl RunePage {
+    /// Decodes a participant's runes with the Data Dragon runes of the patch the game was
+    /// played on.
+    ///
+    /// Data Dragon neither names stat shards nor labels rune vars, `perk_descs` from
+    /// `CommunityDragon::perks` fills both in. Without it shards use their current names.
+    ///
+    /// Returns None if the page is missing its primary or sub style, or references an
+    /// unknown rune.
+    pub fn decode(
+        perks: &Perks,
+        runes: &RuneIndex,
+        perk_descs: Option<&PerkIndex>,
+    ) -> Option<Self> {
+        let primary = perks.primary_style()?;
+        let secondary = perks.sub_style()?;
+        let (keystone, primary_runes) = primary.get_selections().split_first()?;
+        let selections = |selections: &[PerkStyleSelection]| {
+            selections
+                .iter()
+                .map(|selection| Self::selection(selection, runes, perk_descs))
+                .collect::<Option<Vec<_>>>()
+        };
+        let stats = perks.get_stat_perks();
+        Some(Self {
+            primary_tree: Self::tree(primary, runes)?,
+            secondary_tree: Self::tree(secondary, runes)?,
+            keystone: Self::selection(keystone, runes, perk_descs)?,
+            primary_runes: selections(primary_runes)?,
+            secondary_runes: selections(secondary.get_selections())?,
+            stat_shards: StatShards {
+                offense: StatShard::new(stats.get_offense(), perk_descs),
+                flex: StatShard::new(stats.get_flex(), perk_descs),
+                defense: StatShard::new(stats.get_defense(), perk_descs),
+            },
+        })
+    }
+
+    fn tree(style: &PerkStyle, runes: &RuneIndex) -> Option<RuneTreeName> {
+        let tree = runes.tree(style.get_style())?;
+        Some(RuneTreeName {
+            id: tree.id,
+            name: tree.name.clone(),
+        })
+    }
+
+    fn selection(
+        selection: &PerkStyleSelection,
+        runes: &RuneIndex,
+        perk_descs: Option<&PerkIndex>,
+    ) -> Option<RuneSelection> {
+        let rune = runes.lookup(selection.get_perk())?;
+        let vars = selection.get_vars();
+        let stats = match perk_descs.and_then(|perks| perks.lookup(rune.id)) {
+            Some(perk) => perk.stat_descs(vars),
+            None => vars
+                .iter()
+                .enumerate()
+                .filter(|(_, var)| **var != 0)
+                .map(|(i, var)| format!("var{}: {}", i + 1, var))
+                .collect(),
+        };
+        Some(RuneSelection {
+            id: rune.id,
+            name: rune.name().to_string(),
+            vars,
+            stats,
+        })
+    }
+}
+
+impl StatShard {
+    fn new(id: i64, perk_descs: Option<&PerkIndex>) -> Self {
+        let name = match perk_descs.and_then(|perks| perks.name_of(id)) {
+            Some(name) => name.to_string(),
+            None => match stat_shard_name(id) {
+                Some(name) => name.to_string(),
+                None => format!("Unknown shard {}", id),
+            },
+        };
+        Self { id, name }
+    }
+}
+
+/// Current names of the stat shards.
+pub fn stat_shard_name(id: i64) -> Option<&'static str> {
+    let name = match id {
+        5001 => "Health Scaling",
+        5002 => "Armor",
+        5003 => "Magic Resist",
+        5005 => "Attack Speed",
+        5007 => "Ability Haste",
+        5008 => "Adaptive Force",
+        5010 => "Move Speed",
+        5011 => "Health",
+        5013 => "Tenacity and Slow Resist",
+        _ => return None,
+    };
+    Some(name)
+}
+
+impl fmt::Display for RuneSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.stats.is_empty() {
+            write!(f, " ({})", self.stats.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RunePage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.primary_tree.name, self.keystone)?;
+        for rune in &self.primary_runes {
+            writeln!(f, "  {}", rune)?;
+        }
+        writeln!(f, "{}", self.secondary_tree.name)?;
+        for rune in &self.secondary_runes {
+            writeln!(f, "  {}", rune)?;
+        }
+        write!(
+            f,
+            "Shards: {}, {}, {}",
+            self.stat_shards.offense.name,
+            self.stat_shards.flex.name,
+            self.stat_shards.defense.name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ddragon::rune::RuneTree;
+
+    const RUNES: &str = r#"[
+        {"id": 8000, "key": "Precision", "icon": "p.png", "name": "Precision", "slots": [
+            {"runes": [{"id": 8010, "key": "Conqueror", "icon": "c.png", "name": "Conqueror", "shortDesc": "", "longDesc": ""}]},
+            {"runes": [{"id": 9111, "key": "Triumph", "icon": "t.png", "name": "Triumph", "shortDesc": "", "longDesc": ""}]},
+            {"runes": [{"id": 9104, "key": "LegendAlacrity", "icon": "a.png", "name": "Legend: Alacrity", "shortDesc": "", "longDesc": ""}]},
+            {"runes": [{"id": 8299, "key": "LastStand", "icon": "l.png", "name": "Last Stand", "shortDesc": "", "longDesc": ""}]}
+        ]},
+        {"id": 8400, "key": "Resolve", "icon": "r.png", "name": "Resolve", "slots": [
+            {"runes": [{"id": 8437, "key": "GraspOfTheUndying", "icon": "g.png", "name": "Grasp of the Undying", "shortDesc": "", "longDesc": ""}]},
+            {"runes": [{"id": 8473, "key": "BonePlating", "icon": "b.png", "name": "Bone Plating", "shortDesc": "", "longDesc": ""}]},
+            {"runes": [{"id": 8451, "key": "Overgrowth", "icon": "o.png", "name": "Overgrowth", "shortDesc": "", "longDesc": ""}]}
+        ]}
+    ]"#;
+
+    /// `Participant.perks` of a match-v5 response, with the sub style listed first.
+    const PERKS: &str = r#"{
+        "statPerks": {"defense": 5001, "flex": 5008, "offense": 5005},
+        "styles": [
+            {"description": "subStyle", "selections": [
+                {"perk": 8473, "var1": 890, "var2": 0, "var3": 0},
+                {"perk": 8451, "var1": 120, "var2": 0, "var3": 0}
+            ], "style": 8400},
+            {"description": "primaryStyle", "selections": [
+                {"perk": 8010, "var1": 1234, "var2": 0, "var3": 0},
+                {"perk": 9111, "var1": 567, "var2": 60, "var3": 0},
+                {"perk": 9104, "var1": 12, "var2": 0, "var3": 0},
+                {"perk": 8299, "var1": 345, "var2": 0, "var3": 0}
+            ], "style": 8000}
+        ]
+    }"#;
+
+    fn runes() -> RuneIndex {
+        let trees: Vec<RuneTree> = serde_json::from_str(RUNES).unwrap();
+        RuneIndex::new("14.23.1".to_string(), trees)
+    }
+
+    fn names(selections: &[RuneSelection]) -> Vec<&str> {
+        selections
+            .iter()
+            .map(|selection| selection.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn decodes_styles_by_description() {
+        let perks: Perks = serde_json::from_str(PERKS).unwrap();
+        let page = RunePage::decode(&perks, &runes(), None).unwrap();
+        assert_eq!(page.primary_tree.name, "Precision");
+        assert_eq!(page.secondary_tree.name, "Resolve");
+        assert_eq!(page.keystone.name, "Conqueror");
+        assert_eq!(page.keystone.stats, vec!["var1: 1234"]);
+        assert_eq!(
+            names(&page.primary_runes),
+            vec!["Triumph", "Legend: Alacrity", "Last Stand"]
+        );
+        assert_eq!(
+            names(&page.secondary_runes),
+            vec!["Bone Plating", "Overgrowth"]
+        );
+        assert_eq!(page.stat_shards.offense.name, "Attack Speed");
+        assert_eq!(page.stat_shards.flex.name, "Adaptive Force");
+        assert_eq!(page.stat_shards.defense.name, "Health Scaling");
+
+        let text = page.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Precision: Conqueror (var1: 1234)");
+        assert_eq!(lines[4], "Resolve");
+        assert_eq!(lines[5], "  Bone Plating (var1: 890)");
+        assert_eq!(
+            lines[7],
+            "Shards: Attack Speed, Adaptive Force, Health Scaling"
+        );
+    }
+
+    #[test]
+    fn splits_pages_with_fewer_runes_by_style() {
+        // A single secondary rune must not take the last primary rune with it
+        let perks: Perks = serde_json::from_str(
+            r#"{
+                "statPerks": {"defense": 5001, "flex": 5008, "offense": 5005},
+                "styles": [
+                    {"description": "primaryStyle", "selections": [
+                        {"perk": 8010, "var1": 0, "var2": 0, "var3": 0},
+                        {"perk": 9111, "var1": 0, "var2": 0, "var3": 0},
+                        {"perk": 9104, "var1": 0, "var2": 0, "var3": 0},
+                        {"perk": 8299, "var1": 0, "var2": 0, "var3": 0}
+                    ], "style": 8000},
+                    {"description": "subStyle", "selections": [
+                        {"perk": 8473, "var1": 0, "var2": 0, "var3": 0}
+                    ], "style": 8400}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let page = RunePage::decode(&perks, &runes(), None).unwrap();
+        assert_eq!(page.primary_runes.len(), 3);
+        assert_eq!(names(&page.secondary_runes), vec!["Bone Plating"]);
+        assert!(page.keystone.stats.is_empty());
+    }
+
+    #[test]
+    fn rejects_missing_styles_and_unknown_runes() {
+        let perks: Perks = serde_json::from_str(&PERKS.replace("subStyle", "otherStyle")).unwrap();
+        assert!(RunePage::decode(&perks, &runes(), None).is_none());
+        let perks: Perks = serde_json::from_str(&PERKS.replace("8473", "1")).unwrap();
+        assert!(RunePage::decode(&perks, &runes(), None).is_none());
+    }
+}
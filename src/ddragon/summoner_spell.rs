@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use super::{DataFile, Image, StaticEntry, StaticIndex};
+
+pub type SummonerSpellIndex = StaticIndex<i64, SummonerSpell>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerSpell {
+    /// Internal name, e.g. "SummonerFlash"
+    pub id: String,
+    /// Numeric spell ID as a string, matching `Participant.summoner1_id`
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    pub cooldown: Vec<f64>,
+    pub summoner_level: i64,
+    /// Game modes the spell is available in, e.g. "CLASSIC" or "ARAM"
+    #[serde(default)]
+    pub modes: Vec<String>,
+    pub image: Image,
+}
+
+impl StaticEntry for SummonerSpell {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn icon_path(&self) -> Option<String> {
+        Some(self.image.path())
+    }
+}
+
+pub(crate) fn index(file: DataFile<SummonerSpell>) -> SummonerSpellIndex {
+    let mut entries = HashMap::with_capacity(file.data.len());
+    for spell in file.data.into_values() {
+        match spell.key.parse() {
+            Ok(key) => {
+                entries.insert(key, spell);
+            }
+            Err(e) => error!("{e:?}, invalid key for summoner spell '{}'", spell.id),
+        }
+    }
+    StaticIndex {
+        version: file.version,
+        entries,
+    }
+}
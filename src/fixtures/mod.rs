@@ -0,0 +1,126 @@
+//! Synthetic match-v5 matches shared by unit tests.
+//!
+//! `participant.json` holds a participant with every field zeroed, the helpers fill in what
+//! the tests look at. Build the JSON, adjust it if needed, then `parse` it into a `Match`.
+
+use serde_json::{json, Value};
+
+use crate::lol_match::Match;
+
+/// A participant with the given identity and result, everything else zeroed.
+pub(crate) fn participant(
+    puuid: &str,
+    team_id: i64,
+    position: &str,
+    champion_id: i64,
+    win: bool,
+) -> Value {
+    let mut participant: Value = serde_json::from_str(include_str!("participant.json")).unwrap();
+    let fields = json!({
+        "puuid": puuid,
+        "riotIdGameName": puuid,
+        "riotIdTagline": "EUW",
+        "teamId": team_id,
+        "teamPosition": position,
+        "individualPosition": position,
+        "championId": champion_id,
+        "championName": format!("Champion{}", champion_id),
+        "win": win,
+    });
+    for (key, value) in fields.as_object().unwrap() {
+        participant[key] = value.clone();
+    }
+    participant
+}
+
+/// A finished match on patch 14.23 with teams 100 and 200, won by the team with a winning
+/// participant. Participants are numbered in order.
+pub(crate) fn game(
+    match_id: &str,
+    queue_id: i64,
+    game_creation: u64,
+    mut participants: Vec<Value>,
+) -> Value {
+    for (index, participant) in participants.iter_mut().enumerate() {
+        participant["participantId"] = json!(index + 1);
+    }
+    let (platform_id, game_id) = match_id.split_once('_').unwrap();
+    let teams: Vec<Value> = [100, 200]
+        .into_iter()
+        .map(|team_id| {
+            let win = participants
+                .iter()
+                .any(|participant| participant["teamId"] == team_id && participant["win"] == true);
+            json!({
+                "bans": [],
+                "objectives": {
+                    "baron": {"first": false, "kills": 0},
+                    "champion": {"first": false, "kills": 0},
+                    "dragon": {"first": false, "kills": 0},
+                    "horde": {"first": false, "kills": 0},
+                    "inhibitor": {"first": false, "kills": 0},
+                    "riftHerald": {"first": false, "kills": 0},
+                    "tower": {"first": false, "kills": 0}
+                },
+                "teamId": team_id,
+                "win": win
+            })
+        })
+        .collect();
+    json!({
+        "metadata": {
+            "dataVersion": "2",
+            "matchId": match_id,
+            "participants": participants.iter().map(|p| p["puuid"].clone()).collect::<Vec<_>>()
+        },
+        "info": {
+            "endOfGameResult": "GameComplete",
+            "gameCreation": game_creation,
+            "gameDuration": 1800,
+            "gameEndTimestamp": game_creation + 1_800_000,
+            "gameId": game_id.parse::<u64>().unwrap(),
+            "gameMode": "CLASSIC",
+            "gameVersion": "14.23.638.5018",
+            "mapId": 11,
+            "participants": participants,
+            "platformId": platform_id,
+            "queueId": queue_id,
+            "teams": teams,
+            "tournamentCode": null
+        }
+    })
+}
+
+/// Adds a ban to the team with `team_id`.
+pub(crate) fn ban(game: &mut Value, team_id: i64, champion_id: i64) {
+    let teams = game["info"]["teams"].as_array_mut().unwrap();
+    let team = teams
+        .iter_mut()
+        .find(|team| team["teamId"] == team_id)
+        .unwrap();
+    let bans = team["bans"].as_array_mut().unwrap();
+    let pick_turn = bans.len() + 1;
+    bans.push(json!({"championId": champion_id, "pickTurn": pick_turn}));
+}
+
+pub(crate) fn parse(game: Value) -> Match {
+    serde_json::from_value(game).unwrap()
+}
+
+/// A ranked solo match of ten players, "player{n}" playing champion `n` and blue side winning.
+pub(crate) fn ranked_game(match_id: &str, game_creation: u64) -> Match {
+    let positions = ["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY"];
+    let participants = (0..10)
+        .map(|n| {
+            let team_id = if n < 5 { 100 } else { 200 };
+            participant(
+                &format!("player{}", n),
+                team_id,
+                positions[n % 5],
+                n as i64,
+                n < 5,
+            )
+        })
+        .collect();
+    parse(game(match_id, 420, game_creation, participants))
+}
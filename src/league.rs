@@ -1,17 +1,24 @@
 use crate::{
+    cache::{self, Endpoint},
     queue::ranked::RankedQueue,
     ranked::{division::RankedDivision, tier::RankedTier},
     region::server::ServerRegion,
 };
-use log::{debug, error};
+use futures::{stream, Stream, StreamExt};
+use log::error;
 use serde::{Deserialize, Serialize};
 
+/// Number of entries the API returns per page of league-v4 and league-exp-v4 entries.
+pub const ENTRIES_PAGE_SIZE: usize = 205;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LeagueEntry {
     #[serde(rename = "leagueId")]
     league_id: String,
     #[serde(rename = "summonerId")]
     summoner_id: String,
+    /// Missing from responses predating the field
+    puuid: Option<String>,
     #[serde(rename = "queueType")]
     queue_type: RankedQueue,
     tier: RankedTier,
@@ -50,6 +57,7 @@ pub struct LeagueList {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LeagueItem {
+    #[serde(rename = "freshBlood")]
     fresh_blood: bool,
     wins: u32,
     #[serde(rename = "miniSeries")]
@@ -58,15 +66,85 @@ pub struct LeagueItem {
     veteran: bool,
     #[serde(rename = "hotStreak")]
     hot_streak: bool,
-    rank: String,
+    rank: RankedDivision,
     #[serde(rename = "leaguePoints")]
     league_points: u32,
     losses: u32,
     #[serde(rename = "summonerId")]
     summoner_id: String,
+    puuid: Option<String>,
 }
 
 impl LeagueList {
+    /// Requests the apex league of the given tier.
+    ///
+    /// Returns None on error or if the tier is not MASTER, GRANDMASTER or CHALLENGER.
+    pub async fn apex_by_queue(
+        region: &ServerRegion,
+        api_key: &String,
+        tier: RankedTier,
+        queue: RankedQueue,
+    ) -> Option<Self> {
+        match tier {
+            RankedTier::CHALLENGER => Self::challenger_by_queue(region, api_key, queue).await,
+            RankedTier::GRANDMASTER => Self::grandmaster_by_queue(region, api_key, queue).await,
+            RankedTier::MASTER => Self::master_by_queue(region, api_key, queue).await,
+            _ => {
+                error!("'{}' is not an apex tier", tier);
+                None
+            }
+        }
+    }
+
+    /// Streams an apex league as pages of `ENTRIES_PAGE_SIZE` entries, mirroring
+    /// `LeagueEntry::pages`. The whole league is fetched with a single request.
+    pub fn pages(
+        region: ServerRegion,
+        api_key: String,
+        tier: RankedTier,
+        queue: RankedQueue,
+    ) -> impl Stream<Item = Vec<LeagueEntry>> {
+        stream::once(async move { Self::apex_by_queue(&region, &api_key, tier, queue).await })
+            .flat_map(|list| {
+                let mut pages = Vec::new();
+                let mut entries = list.map(Self::into_entries).unwrap_or_default().into_iter();
+                loop {
+                    let page: Vec<LeagueEntry> = entries.by_ref().take(ENTRIES_PAGE_SIZE).collect();
+                    if page.is_empty() {
+                        break;
+                    }
+                    pages.push(page);
+                }
+                stream::iter(pages)
+            })
+    }
+
+    /// Converts the league's items into entries carrying the league's ID, tier and queue.
+    pub fn into_entries(self) -> Vec<LeagueEntry> {
+        let league_id = self.league_id;
+        let tier = self.tier;
+        let queue = self.queue;
+        self.entries
+            .into_iter()
+            .map(|item| LeagueEntry {
+                league_id: league_id.clone(),
+                summoner_id: item.summoner_id,
+                puuid: item.puuid,
+                queue_type: queue,
+                tier,
+                rank: item.rank,
+                league_points: item.league_points,
+                wins: item.wins,
+                losses: item.losses,
+                hot_streak: item.hot_streak,
+                veteran: item.veteran,
+                fresh_blood: item.fresh_blood,
+                inactive: item.inactive,
+                mini_series: item.mini_series,
+            })
+            .collect()
+    }
+
     pub async fn challenger_by_queue(
         region: &ServerRegion,
         api_key: &String,
@@ -74,12 +152,9 @@ impl LeagueList {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/league/v4/challengerleagues/by-queue/{}?api_key={}",
-            region.to_string(),
-            queue.to_string(),
-            api_key
+            region, queue, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::League, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -96,12 +171,9 @@ impl LeagueList {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/league/v4/grandmasterleagues/by-queue/{}?api_key={}",
-            region.to_string(),
-            queue.to_string(),
-            api_key
+            region, queue, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::League, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -118,12 +190,9 @@ impl LeagueList {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/league/v4/masterleagues/by-queue/{}?api_key={}",
-            region.to_string(),
-            queue.to_string(),
-            api_key
+            region, queue, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::League, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -140,12 +209,9 @@ impl LeagueList {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/league/v4/leagues/{}?api_key={}",
-            region.to_string(),
-            league_id,
-            api_key
+            region, league_id, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::League, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -164,12 +230,28 @@ impl LeagueEntry {
     ) -> Option<Vec<Self>> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/league/v4/entries/by-summoner/{}?api_key={}",
-            region.to_string(),
-            summoner_id,
-            api_key
+            region, summoner_id, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::League, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/league/v4/entries/by-puuid/{}?api_key={}",
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::League, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -187,30 +269,43 @@ impl LeagueEntry {
         queue: RankedQueue,
         page: Option<u32>,
     ) -> Option<Vec<Self>> {
-        let request_url: String;
-        if page.is_some() {
-            request_url = format!(
-                "https://{}.api.riotgames.com/lol/league/v4/entries/{}/{}/{}?api_key={}",
-                region.to_string(),
-                queue.to_string(),
-                tier.to_string(),
-                division.to_string(),
-                api_key
-            );
-        } else {
-            request_url = format!(
-                "https://{}.api.riotgames.com/lol/league/v4/entries/{}/{}/{}?page={}&api_key={}",
-                region.to_string(),
-                queue.to_string(),
-                tier.to_string(),
-                division.to_string(),
-                page.unwrap(),
-                api_key
-            );
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/lol/league/v4/entries/{}/{}/{}?api_key={}",
+            region, queue, tier, division, api_key
+        );
+        if let Some(page) = page {
+            request_url = format!("{}&page={}", request_url, page);
+        }
+        let resp = cache::get(Endpoint::League, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
         }
+    }
 
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+    /// Requests a page of entries through league-exp-v4, which unlike league-v4 also
+    /// covers MASTER, GRANDMASTER and CHALLENGER (with division I).
+    ///
+    /// Pages start at 1, an empty page means there are no more entries.
+    pub async fn exp_entries(
+        region: &ServerRegion,
+        api_key: &String,
+        division: RankedDivision,
+        tier: RankedTier,
+        queue: RankedQueue,
+        page: Option<u32>,
+    ) -> Option<Vec<Self>> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/lol/league-exp/v4/entries/{}/{}/{}?api_key={}",
+            region, queue, tier, division, api_key
+        );
+        if let Some(page) = page {
+            request_url = format!("{}&page={}", request_url, page);
+        }
+        let resp = cache::get(Endpoint::League, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -219,4 +314,363 @@ impl LeagueEntry {
             }
         }
     }
+
+    /// Streams every page of a tier and division through league-exp-v4, starting at page 1.
+    ///
+    /// The stream ends after the first empty page or on error.
+    pub fn pages(
+        region: ServerRegion,
+        api_key: String,
+        division: RankedDivision,
+        tier: RankedTier,
+        queue: RankedQueue,
+    ) -> impl Stream<Item = Vec<Self>> {
+        stream::unfold(1, move |page| {
+            let api_key = api_key.clone();
+            async move {
+                let entries =
+                    Self::exp_entries(&region, &api_key, division, tier, queue, Some(page)).await?;
+                if entries.is_empty() {
+                    return None;
+                }
+                Some((entries, page + 1))
+            }
+        })
+    }
+
+    pub fn get_league_id(&self) -> &String {
+        &self.league_id
+    }
+
+    pub fn get_summoner_id(&self) -> &String {
+        &self.summoner_id
+    }
+
+    pub fn get_puuid(&self) -> &Option<String> {
+        &self.puuid
+    }
+
+    pub fn get_queue_type(&self) -> RankedQueue {
+        self.queue_type
+    }
+
+    pub fn get_tier(&self) -> RankedTier {
+        self.tier
+    }
+
+    pub fn get_rank(&self) -> RankedDivision {
+        self.rank
+    }
+
+    pub fn get_league_points(&self) -> u32 {
+        self.league_points
+    }
+
+    pub fn get_wins(&self) -> u32 {
+        self.wins
+    }
+
+    pub fn get_losses(&self) -> u32 {
+        self.losses
+    }
+
+    pub fn get_hot_streak(&self) -> bool {
+        self.hot_streak
+    }
+
+    pub fn get_veteran(&self) -> bool {
+        self.veteran
+    }
+
+    pub fn get_fresh_blood(&self) -> bool {
+        self.fresh_blood
+    }
+
+    pub fn get_inactive(&self) -> bool {
+        self.inactive
+    }
+
+    pub fn get_mini_series(&self) -> &Option<MiniSeries> {
+        &self.mini_series
+    }
+}
+
+impl LeagueItem {
+    pub fn get_summoner_id(&self) -> &String {
+        &self.summoner_id
+    }
+
+    pub fn get_puuid(&self) -> &Option<String> {
+        &self.puuid
+    }
+
+    pub fn get_rank(&self) -> RankedDivision {
+        self.rank
+    }
+
+    pub fn get_league_points(&self) -> u32 {
+        self.league_points
+    }
+
+    pub fn get_wins(&self) -> u32 {
+        self.wins
+    }
+
+    pub fn get_losses(&self) -> u32 {
+        self.losses
+    }
+
+    pub fn get_hot_streak(&self) -> bool {
+        self.hot_streak
+    }
+
+    pub fn get_veteran(&self) -> bool {
+        self.veteran
+    }
+
+    pub fn get_fresh_blood(&self) -> bool {
+        self.fresh_blood
+    }
+
+    pub fn get_inactive(&self) -> bool {
+        self.inactive
+    }
+
+    pub fn get_mini_series(&self) -> &Option<MiniSeries> {
+        &self.mini_series
+    }
+}
+
+impl MiniSeries {
+    pub fn get_wins(&self) -> u32 {
+        self.wins
+    }
+
+    pub fn get_losses(&self) -> u32 {
+        self.losses
+    }
+
+    pub fn get_target(&self) -> u32 {
+        self.target
+    }
+
+    /// One character per game of the series, "W" for a win, "L" for a loss and "N" for a
+    /// game not played yet
+    pub fn get_progress(&self) -> &String {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::cache::{cache_key, Cache, LruCache, ResponseCache};
+
+    const API_KEY: &str = "RGAPI-1";
+    const EXP_URL: &str =
+        "https://euw1.api.riotgames.com/lol/league-exp/v4/entries/RANKED_SOLO_5X5/GOLD/II";
+    const CHALLENGER_URL: &str =
+        "https://euw1.api.riotgames.com/lol/league/v4/challengerleagues/by-queue/RANKED_SOLO_5X5";
+
+    fn item_json(summoner_id: &str) -> Value {
+        json!({
+            "freshBlood": true,
+            "wins": 10,
+            "miniSeries": {"losses": 1, "progress": "WLN", "target": 2, "wins": 1},
+            "inactive": false,
+            "veteran": false,
+            "hotStreak": true,
+            "rank": "I",
+            "leaguePoints": 1000,
+            "losses": 5,
+            "summonerId": summoner_id,
+            "puuid": summoner_id,
+        })
+    }
+
+    fn entry_json(summoner_id: &str) -> Value {
+        let mut entry = item_json(summoner_id);
+        entry["leagueId"] = "league".into();
+        entry["queueType"] = "RANKED_SOLO_5x5".into();
+        entry["tier"] = "GOLD".into();
+        entry["rank"] = "II".into();
+        entry
+    }
+
+    /// Responses served from a scoped cache, so requests in `Cache::scope` are never sent.
+    fn cache(responses: &[(&str, Value)]) -> Cache {
+        let backend = Arc::new(LruCache::new(100));
+        for (url, body) in responses {
+            let key = cache_key(Endpoint::League, url);
+            backend.insert(key, body.to_string(), None);
+        }
+        let cache = Cache::new();
+        cache.set_backend(backend);
+        cache
+    }
+
+    fn summoner_ids(entries: &[LeagueEntry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|entry| entry.get_summoner_id().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn deserializes_entries() {
+        let entry: LeagueEntry = serde_json::from_value(entry_json("s1")).unwrap();
+        assert!(entry.get_fresh_blood());
+        assert!(entry.get_hot_streak());
+        assert_eq!(entry.get_rank(), RankedDivision::II);
+        assert_eq!(entry.get_tier(), RankedTier::GOLD);
+        assert_eq!(entry.get_queue_type(), RankedQueue::RankedSolo5x5);
+        assert_eq!(
+            entry.get_mini_series().as_ref().unwrap().get_progress(),
+            "WLN"
+        );
+
+        let item: LeagueItem = serde_json::from_value(item_json("s1")).unwrap();
+        assert!(item.get_fresh_blood());
+        assert_eq!(item.get_rank(), RankedDivision::I);
+
+        let mut without_puuid = entry_json("s1");
+        without_puuid.as_object_mut().unwrap().remove("puuid");
+        let entry: LeagueEntry = serde_json::from_value(without_puuid).unwrap();
+        assert!(entry.get_puuid().is_none());
+
+        let mut invalid_rank = item_json("s1");
+        invalid_rank["rank"] = "V".into();
+        assert!(serde_json::from_value::<LeagueItem>(invalid_rank).is_err());
+    }
+
+    #[tokio::test]
+    async fn entries_request_the_given_page() {
+        let url = "https://euw1.api.riotgames.com/lol/league/v4/entries/RANKED_SOLO_5X5/GOLD/II";
+        let cache = cache(&[
+            (url, json!([entry_json("first")])),
+            (&format!("{}?page=2", url), json!([entry_json("second")])),
+        ]);
+        let entries = |page| {
+            let api_key = API_KEY.to_string();
+            cache.scope(async move {
+                LeagueEntry::entries(
+                    &ServerRegion::EUW1,
+                    &api_key,
+                    RankedDivision::II,
+                    RankedTier::GOLD,
+                    RankedQueue::RankedSolo5x5,
+                    page,
+                )
+                .await
+                .unwrap()
+            })
+        };
+        assert_eq!(summoner_ids(&entries(None).await), vec!["first"]);
+        assert_eq!(summoner_ids(&entries(Some(2)).await), vec!["second"]);
+    }
+
+    #[tokio::test]
+    async fn pages_stop_at_the_first_empty_page() {
+        let cache = cache(&[
+            (
+                &format!("{}?page=1", EXP_URL),
+                json!([entry_json("s1"), entry_json("s2")]),
+            ),
+            (&format!("{}?page=2", EXP_URL), json!([entry_json("s3")])),
+            (&format!("{}?page=3", EXP_URL), json!([])),
+            (&format!("{}?page=4", EXP_URL), json!([entry_json("s4")])),
+        ]);
+        let pages: Vec<Vec<LeagueEntry>> = cache
+            .scope(
+                LeagueEntry::pages(
+                    ServerRegion::EUW1,
+                    API_KEY.to_string(),
+                    RankedDivision::II,
+                    RankedTier::GOLD,
+                    RankedQueue::RankedSolo5x5,
+                )
+                .collect(),
+            )
+            .await;
+        let pages: Vec<Vec<&str>> = pages.iter().map(|page| summoner_ids(page)).collect();
+        assert_eq!(pages, vec![vec!["s1", "s2"], vec!["s3"]]);
+    }
+
+    #[tokio::test]
+    async fn pages_stop_on_error() {
+        let cache = cache(&[
+            (&format!("{}?page=1", EXP_URL), json!([entry_json("s1")])),
+            (&format!("{}?page=2", EXP_URL), json!({})),
+        ]);
+        let pages: Vec<Vec<LeagueEntry>> = cache
+            .scope(
+                LeagueEntry::pages(
+                    ServerRegion::EUW1,
+                    API_KEY.to_string(),
+                    RankedDivision::II,
+                    RankedTier::GOLD,
+                    RankedQueue::RankedSolo5x5,
+                )
+                .collect(),
+            )
+            .await;
+        assert_eq!(pages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn apex_leagues_are_split_into_pages() {
+        let items: Vec<Value> = (0..ENTRIES_PAGE_SIZE + 1)
+            .map(|i| item_json(&format!("s{}", i)))
+            .collect();
+        let league = json!({
+            "leagueId": "challenger",
+            "entries": items,
+            "tier": "CHALLENGER",
+            "name": "name",
+            "queue": "RANKED_SOLO_5x5",
+        });
+        let cache = cache(&[(CHALLENGER_URL, league)]);
+        let pages: Vec<Vec<LeagueEntry>> = cache
+            .scope(
+                LeagueList::pages(
+                    ServerRegion::EUW1,
+                    API_KEY.to_string(),
+                    RankedTier::CHALLENGER,
+                    RankedQueue::RankedSolo5x5,
+                )
+                .collect(),
+            )
+            .await;
+        assert_eq!(
+            pages.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![ENTRIES_PAGE_SIZE, 1]
+        );
+        let last = &pages[1][0];
+        assert_eq!(last.get_summoner_id(), &format!("s{}", ENTRIES_PAGE_SIZE));
+        assert_eq!(last.get_league_id(), "challenger");
+        assert_eq!(last.get_tier(), RankedTier::CHALLENGER);
+        assert_eq!(last.get_queue_type(), RankedQueue::RankedSolo5x5);
+        assert!(last.get_fresh_blood());
+    }
+
+    #[tokio::test]
+    async fn non_apex_tiers_have_no_apex_league() {
+        let cache = cache(&[]);
+        let pages: Vec<Vec<LeagueEntry>> = cache
+            .scope(
+                LeagueList::pages(
+                    ServerRegion::EUW1,
+                    API_KEY.to_string(),
+                    RankedTier::GOLD,
+                    RankedQueue::RankedSolo5x5,
+                )
+                .collect(),
+            )
+            .await;
+        assert!(pages.is_empty());
+    }
 }
@@ -1,7 +1,31 @@
 pub mod account;
+pub mod cache;
+#[cfg(feature = "callback")]
+pub mod callback;
+pub mod cdragon;
+pub mod challenges;
+pub mod champion;
+pub mod champion_mastery;
+pub mod clash;
+#[cfg(feature = "crawler")]
+pub mod crawler;
+pub mod ddragon;
+#[cfg(test)]
+mod fixtures;
 pub mod league;
 pub mod lol_match;
+pub mod lor;
+pub mod patch;
+pub mod player_sync;
 pub mod queue;
 pub mod ranked;
 pub mod region;
+pub mod spectator;
+pub mod stats;
+pub mod status;
+#[cfg(feature = "storage")]
+pub mod storage;
 pub mod summoner;
+pub mod tft;
+pub mod tournament;
+pub mod val;
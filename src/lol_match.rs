@@ -1,5 +1,10 @@
-use crate::{queue::queue_type::QueueType, region::routing::RoutingRegion};
-use log::{debug, error};
+use crate::{
+    cache::{self, Endpoint},
+    patch::Patch,
+    queue::queue_type::QueueType,
+    region::routing::RoutingRegion,
+};
+use log::error;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -30,6 +35,9 @@ pub struct Info {
     pub game_id: u64,
     #[serde(rename = "gameMode")]
     pub game_mode: String,
+    /// Full client version, e.g. "14.23.636.1234", empty if the payload has none
+    #[serde(rename = "gameVersion", default)]
+    pub game_version: String,
     #[serde(rename = "mapId")]
     pub map_id: i64,
     pub participants: Vec<Participant>,
@@ -42,6 +50,20 @@ pub struct Info {
     pub tournament_code: Option<String>,
 }
 
+impl Info {
+    /// Patch the game was played on, None if the game version could not be parsed.
+    pub fn patch(&self) -> Option<Patch> {
+        Patch::from_version(&self.game_version)
+    }
+
+    /// Whether the game was remade, remakes are usually left out of statistics.
+    pub fn is_remake(&self) -> bool {
+        self.participants
+            .iter()
+            .any(|participant| participant.game_ended_in_early_surrender)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Participant {
@@ -206,6 +228,120 @@ pub struct Participant {
     win: bool,
 }
 
+impl Participant {
+    /// Arena augments picked by the participant, resolvable through
+    /// `CommunityDragon::arena_augments`. Empty outside of arena.
+    pub fn augment_ids(&self) -> Vec<i64> {
+        [
+            self.player_augment_1,
+            self.player_augment_2,
+            self.player_augment_3,
+            self.player_augment_4,
+        ]
+        .into_iter()
+        .filter(|id| *id != 0)
+        .collect()
+    }
+
+    pub fn get_perks(&self) -> &Perks {
+        &self.perks
+    }
+
+    pub fn get_puuid(&self) -> &String {
+        &self.puuid
+    }
+
+    pub fn get_participant_id(&self) -> i64 {
+        self.participant_id
+    }
+
+    pub fn get_team_id(&self) -> i64 {
+        self.team_id
+    }
+
+    pub fn get_champion_id(&self) -> i64 {
+        self.champion_id
+    }
+
+    pub fn get_champion_name(&self) -> &String {
+        &self.champion_name
+    }
+
+    /// "TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY" or empty outside of Summoner's Rift
+    pub fn get_team_position(&self) -> &String {
+        &self.team_position
+    }
+
+    pub fn get_win(&self) -> bool {
+        self.win
+    }
+
+    pub fn get_kills(&self) -> i64 {
+        self.kills
+    }
+
+    pub fn get_deaths(&self) -> i64 {
+        self.deaths
+    }
+
+    pub fn get_assists(&self) -> i64 {
+        self.assists
+    }
+
+    pub fn get_champ_level(&self) -> i64 {
+        self.champ_level
+    }
+
+    pub fn get_gold_earned(&self) -> i64 {
+        self.gold_earned
+    }
+
+    pub fn get_total_damage_dealt_to_champions(&self) -> i64 {
+        self.total_damage_dealt_to_champions
+    }
+
+    pub fn get_total_minions_killed(&self) -> i64 {
+        self.total_minions_killed
+    }
+
+    pub fn get_neutral_minions_killed(&self) -> i64 {
+        self.neutral_minions_killed
+    }
+
+    pub fn get_vision_score(&self) -> i64 {
+        self.vision_score
+    }
+
+    pub fn get_summoner1_id(&self) -> i64 {
+        self.summoner1_id
+    }
+
+    pub fn get_summoner2_id(&self) -> i64 {
+        self.summoner2_id
+    }
+
+    pub fn get_riot_id_game_name(&self) -> &String {
+        &self.riot_id_game_name
+    }
+
+    pub fn get_riot_id_tagline(&self) -> &String {
+        &self.riot_id_tagline
+    }
+
+    /// `item_0..6`, zero for empty slots
+    pub fn get_items(&self) -> [i64; 7] {
+        [
+            self.item_0,
+            self.item_1,
+            self.item_2,
+            self.item_3,
+            self.item_4,
+            self.item_5,
+            self.item_6,
+        ]
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Challenges {
@@ -411,6 +547,63 @@ pub struct PerkStyle {
     style: i64,
 }
 
+impl Perks {
+    pub fn get_stat_perks(&self) -> &PerkStats {
+        &self.stat_perks
+    }
+
+    /// Usually the primary style followed by the secondary style, see `primary_style` and
+    /// `sub_style`
+    pub fn get_styles(&self) -> &Vec<PerkStyle> {
+        &self.styles
+    }
+
+    /// The style described as "primaryStyle", holding the keystone.
+    pub fn primary_style(&self) -> Option<&PerkStyle> {
+        self.style("primaryStyle")
+    }
+
+    /// The style described as "subStyle".
+    pub fn sub_style(&self) -> Option<&PerkStyle> {
+        self.style("subStyle")
+    }
+
+    fn style(&self, description: &str) -> Option<&PerkStyle> {
+        self.styles
+            .iter()
+            .find(|style| style.description == description)
+    }
+}
+
+impl PerkStats {
+    pub fn get_offense(&self) -> i64 {
+        self.offense
+    }
+
+    pub fn get_flex(&self) -> i64 {
+        self.flex
+    }
+
+    pub fn get_defense(&self) -> i64 {
+        self.defense
+    }
+}
+
+impl PerkStyle {
+    /// "primaryStyle" or "subStyle"
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+
+    pub fn get_selections(&self) -> &Vec<PerkStyleSelection> {
+        &self.selections
+    }
+
+    pub fn get_style(&self) -> i64 {
+        self.style
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PerkStyleSelection {
@@ -420,6 +613,17 @@ pub struct PerkStyleSelection {
     var3: i64,
 }
 
+impl PerkStyleSelection {
+    pub fn get_perk(&self) -> i64 {
+        self.perk
+    }
+
+    /// End of game stats tracked by the rune, labelled by `Perk::end_of_game_stat_descs`
+    pub fn get_vars(&self) -> [i64; 3] {
+        [self.var1, self.var2, self.var3]
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Team {
@@ -436,6 +640,31 @@ pub struct Ban {
     pick_turn: i64,
 }
 
+impl Team {
+    pub fn get_bans(&self) -> &Vec<Ban> {
+        &self.bans
+    }
+
+    pub fn get_team_id(&self) -> i64 {
+        self.team_id
+    }
+
+    pub fn get_win(&self) -> bool {
+        self.win
+    }
+}
+
+impl Ban {
+    /// -1 if the team did not ban
+    pub fn get_champion_id(&self) -> i64 {
+        self.champion_id
+    }
+
+    pub fn get_pick_turn(&self) -> i64 {
+        self.pick_turn
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Objectives {
@@ -456,6 +685,11 @@ pub struct Objective {
 }
 
 impl Match {
+    /// Match-v5 ID of a game, e.g. "NA1_1234567890" for game 1234567890 played on "na1".
+    pub fn id_from_game(platform_id: &str, game_id: u64) -> String {
+        format!("{}_{}", platform_id.to_uppercase(), game_id)
+    }
+
     pub async fn by_match_id(
         region: &RoutingRegion,
         api_key: &String,
@@ -463,12 +697,9 @@ impl Match {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/match/v5/matches/{}?api_key={}",
-            region.to_string(),
-            match_id,
-            api_key
+            region, match_id, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get_match(Endpoint::Match, match_id, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -478,6 +709,7 @@ impl Match {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn by_puuid(
         region: &RoutingRegion,
         api_key: &String,
@@ -491,33 +723,31 @@ impl Match {
     ) -> Option<Vec<String>> {
         let mut request_url = format!(
             "https://{}.api.riotgames.com/lol/match/v5/matches/by-puuid/{}/ids",
-            region.to_string(),
-            puuid,
+            region, puuid,
         );
         // TODO, find a better looking way to do this?
-        if start.is_some() {
-            request_url = format!("{}?start={}", request_url, start.unwrap());
+        if let Some(start) = start {
+            request_url = format!("{}?start={}", request_url, start);
         } else {
             request_url = format!("{}?start=0", request_url);
         }
-        if start_time.is_some() {
-            request_url = format!("{}startTime={}", request_url, start_time.unwrap());
+        if let Some(start_time) = start_time {
+            request_url = format!("{}&startTime={}", request_url, start_time);
         }
-        if end_time.is_some() {
-            request_url = format!("{}&endTime={}", request_url, end_time.unwrap());
+        if let Some(end_time) = end_time {
+            request_url = format!("{}&endTime={}", request_url, end_time);
         }
-        if queue.is_some() {
-            request_url = format!("{}&queue={}", request_url, queue.unwrap());
+        if let Some(queue) = queue {
+            request_url = format!("{}&queue={}", request_url, queue);
         }
-        if queue_type.is_some() {
-            request_url = format!("{}&type={}", request_url, queue_type.unwrap());
+        if let Some(queue_type) = queue_type {
+            request_url = format!("{}&type={}", request_url, queue_type);
         }
-        if count.is_some() {
-            request_url = format!("{}&count={}", request_url, count.unwrap());
+        if let Some(count) = count {
+            request_url = format!("{}&count={}", request_url, count);
         }
         request_url = format!("{}&api_key={}", request_url, api_key);
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::MatchIds, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -677,12 +907,9 @@ impl Timeline {
     ) -> Option<Timeline> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/match/v5/matches/{}/timeline?api_key={}",
-            region.to_string(),
-            match_id,
-            api_key
+            region, match_id, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get_match(Endpoint::Timeline, match_id, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -691,4 +918,29 @@ impl Timeline {
             }
         }
     }
+
+    /// Requests the timeline as the JSON body returned by the API, for storing timelines
+    /// whole.
+    pub async fn raw_by_match_id(
+        region: &RoutingRegion,
+        api_key: &String,
+        match_id: &String,
+    ) -> Option<String> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/match/v5/matches/{}/timeline?api_key={}",
+            region, match_id, api_key
+        );
+        let resp = cache::get_match(Endpoint::Timeline, match_id, request_url).await;
+        match serde_json::from_str::<serde_json::Value>(&resp) {
+            Ok(t) if t.get("info").is_some() => Some(resp),
+            Ok(_) => {
+                error!("Not a timeline, {:?}", resp);
+                None
+            }
+            Err(e) => {
+                error!("{e:?}, {:?}", resp);
+                None
+            }
+        }
+    }
 }
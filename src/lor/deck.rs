@@ -0,0 +1,105 @@
+//! lor-deck-v1 and lor-inventory-v1, both of which act on the player who authorised the
+//! given RSO access token.
+
+use crate::{
+    lor::deck_code::{self, CardCodeAndCount, DeckCodeError},
+    region::routing::RoutingRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Deck {
+    pub id: String,
+    pub name: String,
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NewDeck {
+    pub name: String,
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Card {
+    pub code: String,
+    /// Number of copies owned, sent as a string
+    pub count: String,
+}
+
+impl Deck {
+    pub async fn by_access_token(
+        region: &RoutingRegion,
+        access_token: &String,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!("https://{}.api.riotgames.com/lor/deck/v1/decks/me", region);
+        let resp = reqwest::Client::new()
+            .get(request_url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .unwrap();
+        let resp = resp.text().await.expect("Could not parse");
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub fn cards(&self) -> Result<Vec<CardCodeAndCount>, DeckCodeError> {
+        deck_code::decode(&self.code)
+    }
+}
+
+impl NewDeck {
+    /// Adds the deck to the player's collection, returning the new deck's ID.
+    pub async fn create(&self, region: &RoutingRegion, access_token: &String) -> Option<String> {
+        let request_url = format!("https://{}.api.riotgames.com/lor/deck/v1/decks/me", region);
+        let resp = reqwest::Client::new()
+            .post(request_url)
+            .bearer_auth(access_token)
+            .json(self)
+            .send()
+            .await
+            .unwrap();
+        let resp = resp.text().await.expect("Could not parse");
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl Card {
+    /// Requests the player's card collection.
+    pub async fn inventory_by_access_token(
+        region: &RoutingRegion,
+        access_token: &String,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lor/inventory/v1/cards/me",
+            region
+        );
+        let resp = reqwest::Client::new()
+            .get(request_url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .unwrap();
+        let resp = resp.text().await.expect("Could not parse");
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
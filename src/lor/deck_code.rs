@@ -0,0 +1,404 @@
+//! Encoding and decoding of Legends of Runeterra deck codes.
+//!
+//! A deck code is the base32 encoding of a format/version byte followed by varints. Cards are
+//! grouped by copy count (3, 2, then 1) and within that by set and faction, any cards with
+//! more than 3 copies follow individually.
+
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+const FORMAT: u8 = 1;
+const MAX_KNOWN_VERSION: u8 = 5;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Faction identifier, code and the deck code version that introduced it.
+const FACTIONS: [(u64, &str, u8); 11] = [
+    (0, "DE", 1),
+    (1, "FR", 1),
+    (2, "IO", 1),
+    (3, "NX", 1),
+    (4, "PZ", 1),
+    (5, "SI", 1),
+    (6, "BW", 2),
+    (7, "SH", 3),
+    (9, "MT", 2),
+    (10, "BC", 4),
+    (12, "RU", 5),
+];
+
+/// Card numbers sharing a set and faction ID.
+type CardGroup = ((u64, u64), Vec<u64>);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CardCodeAndCount {
+    /// e.g. "01DE001", two digit set, faction code and three digit card number
+    pub card_code: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckCodeError {
+    InvalidBase32,
+    /// The code ended in the middle of a varint or card group
+    Truncated,
+    InvalidFormat(u8),
+    UnsupportedVersion(u8),
+    UnknownFactionId(u64),
+    InvalidCardCode(String),
+    InvalidCount(String),
+}
+
+impl fmt::Display for DeckCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::InvalidBase32 => write!(f, "deck code is not valid base32"),
+            Self::Truncated => write!(f, "deck code ended unexpectedly"),
+            Self::InvalidFormat(format) => write!(f, "unknown deck code format {}", format),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported deck code version {}", version)
+            }
+            Self::UnknownFactionId(id) => write!(f, "unknown faction id {}", id),
+            Self::InvalidCardCode(code) => write!(f, "invalid card code '{}'", code),
+            Self::InvalidCount(code) => write!(f, "card '{}' has a count of 0", code),
+        }
+    }
+}
+
+impl std::error::Error for DeckCodeError {}
+
+/// Decodes a deck code into its cards.
+pub fn decode(code: &str) -> Result<Vec<CardCodeAndCount>, DeckCodeError> {
+    let bytes = base32_decode(code)?;
+    let (&format_and_version, mut rest) = bytes.split_first().ok_or(DeckCodeError::Truncated)?;
+    let format = format_and_version >> 4;
+    let version = format_and_version & 0xF;
+    if format != FORMAT {
+        return Err(DeckCodeError::InvalidFormat(format));
+    }
+    if version > MAX_KNOWN_VERSION {
+        return Err(DeckCodeError::UnsupportedVersion(version));
+    }
+
+    let mut cards = Vec::new();
+    for count in (1..=3).rev() {
+        let group_count = read_varint(&mut rest)?;
+        for _ in 0..group_count {
+            let cards_in_group = read_varint(&mut rest)?;
+            let set = read_varint(&mut rest)?;
+            let faction = faction_code(read_varint(&mut rest)?)?;
+            for _ in 0..cards_in_group {
+                let number = read_varint(&mut rest)?;
+                cards.push(CardCodeAndCount {
+                    card_code: card_code(set, faction, number),
+                    count,
+                });
+            }
+        }
+    }
+    while !rest.is_empty() {
+        let count = read_varint(&mut rest)? as u32;
+        let set = read_varint(&mut rest)?;
+        let faction = faction_code(read_varint(&mut rest)?)?;
+        let number = read_varint(&mut rest)?;
+        cards.push(CardCodeAndCount {
+            card_code: card_code(set, faction, number),
+            count,
+        });
+    }
+    Ok(cards)
+}
+
+/// Encodes cards into a deck code.
+///
+/// Groups are ordered like Riot's reference implementation, smaller groups first and groups
+/// of the same size in the order their first card appears in `cards`. Cards decoded from a
+/// code therefore encode back to the same code.
+pub fn encode(cards: &[CardCodeAndCount]) -> Result<String, DeckCodeError> {
+    let mut version = 1;
+    // Groups of each count, in the order they first appear
+    let mut by_count: [Vec<CardGroup>; 3] = Default::default();
+    let mut many: Vec<(&String, u32, u64, u64, u64)> = Vec::new();
+    for card in cards {
+        let (set, faction, number) = parse_card_code(&card.card_code)?;
+        let faction_version = FACTIONS
+            .iter()
+            .find(|(id, _, _)| *id == faction)
+            .map(|(_, _, version)| *version)
+            .unwrap_or(MAX_KNOWN_VERSION);
+        version = version.max(faction_version);
+        match card.count {
+            0 => return Err(DeckCodeError::InvalidCount(card.card_code.clone())),
+            1..=3 => {
+                let groups = &mut by_count[card.count as usize - 1];
+                match groups.iter_mut().find(|(key, _)| *key == (set, faction)) {
+                    Some((_, numbers)) => numbers.push(number),
+                    None => groups.push(((set, faction), vec![number])),
+                }
+            }
+            count => many.push((&card.card_code, count, set, faction, number)),
+        }
+    }
+
+    let mut bytes = vec![(FORMAT << 4) | version];
+    for mut groups in by_count.into_iter().rev() {
+        // Stable, so groups of the same size keep their order
+        groups.sort_by_key(|(_, numbers)| numbers.len());
+        write_varint(&mut bytes, groups.len() as u64);
+        for ((set, faction), mut numbers) in groups {
+            numbers.sort_unstable();
+            write_varint(&mut bytes, numbers.len() as u64);
+            write_varint(&mut bytes, set);
+            write_varint(&mut bytes, faction);
+            for number in numbers {
+                write_varint(&mut bytes, number);
+            }
+        }
+    }
+    many.sort_by(|a, b| a.0.cmp(b.0));
+    for (_, count, set, faction, number) in many {
+        write_varint(&mut bytes, count as u64);
+        write_varint(&mut bytes, set);
+        write_varint(&mut bytes, faction);
+        write_varint(&mut bytes, number);
+    }
+    Ok(base32_encode(&bytes))
+}
+
+fn faction_code(id: u64) -> Result<&'static str, DeckCodeError> {
+    FACTIONS
+        .iter()
+        .find(|(faction_id, _, _)| *faction_id == id)
+        .map(|(_, code, _)| *code)
+        .ok_or(DeckCodeError::UnknownFactionId(id))
+}
+
+fn card_code(set: u64, faction: &str, number: u64) -> String {
+    format!("{:02}{}{:03}", set, faction, number)
+}
+
+fn parse_card_code(code: &str) -> Result<(u64, u64, u64), DeckCodeError> {
+    let invalid = || DeckCodeError::InvalidCardCode(code.to_string());
+    if code.len() != 7 || !code.is_ascii() {
+        return Err(invalid());
+    }
+    let set = code[0..2].parse().map_err(|_| invalid())?;
+    let faction = FACTIONS
+        .iter()
+        .find(|(_, faction, _)| *faction == &code[2..4])
+        .map(|(id, _, _)| *id)
+        .ok_or_else(invalid)?;
+    let number = code[4..7].parse().map_err(|_| invalid())?;
+    Ok((set, faction, number))
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, DeckCodeError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or(DeckCodeError::Truncated)?;
+        *bytes = rest;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DeckCodeError::Truncated)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn base32_decode(code: &str) -> Result<Vec<u8>, DeckCodeError> {
+    let mut bytes = Vec::with_capacity(code.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in code.trim_end_matches('=').bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase())
+            .ok_or(DeckCodeError::InvalidBase32)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Unpadded RFC 4648 base32, as used by the game client.
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        code.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deck codes from the README and test data of Riot's reference implementation.
+    const REFERENCE_CODES: [&str; 2] = [
+        "CEBAIAIFB4WDANQIAEAQGDAUDAQSIJZUAIAQCBIFAEAQCBAA",
+        "CEAAECABAQJRWHBIFU2DOOYIAEBAMCIMCINCILJZAICACBANE4VCYBABAILR2HRL",
+    ];
+
+    fn card(card_code: &str, count: u32) -> CardCodeAndCount {
+        CardCodeAndCount {
+            card_code: card_code.to_string(),
+            count,
+        }
+    }
+
+    fn code_of(bytes: &[u8]) -> String {
+        base32_encode(bytes)
+    }
+
+    #[test]
+    fn decodes_readme_deck() {
+        let cards = decode(REFERENCE_CODES[0]).unwrap();
+        let expected = [
+            ("01SI015", 3),
+            ("01SI044", 3),
+            ("01SI048", 3),
+            ("01SI054", 3),
+            ("01FR003", 3),
+            ("01FR012", 3),
+            ("01FR020", 3),
+            ("01FR024", 3),
+            ("01FR033", 3),
+            ("01FR036", 3),
+            ("01FR039", 3),
+            ("01FR052", 3),
+            ("01SI005", 2),
+            ("01FR004", 2),
+        ];
+        let expected: Vec<CardCodeAndCount> = expected
+            .iter()
+            .map(|(code, count)| card(code, *count))
+            .collect();
+        assert_eq!(cards, expected);
+    }
+
+    #[test]
+    fn reference_codes_round_trip() {
+        for code in REFERENCE_CODES {
+            let cards = decode(code).unwrap();
+            assert_eq!(cards.iter().map(|card| card.count).sum::<u32>(), 40);
+            assert_eq!(encode(&cards).unwrap(), code);
+            assert_eq!(decode(&code.to_lowercase()).unwrap(), cards);
+        }
+    }
+
+    #[test]
+    fn round_trips_newer_factions_and_large_counts() {
+        let cards = vec![
+            card("01DE001", 3),
+            card("02BW008", 2),
+            card("04SH020", 1),
+            card("05BC011", 1),
+            card("06RU002", 1),
+            card("01IO012", 4),
+            card("01NX020", 6),
+        ];
+        let code = encode(&cards).unwrap();
+        // Version of the newest faction, Runeterra
+        assert_eq!(base32_decode(&code).unwrap()[0], 0x15);
+
+        let mut decoded = decode(&code).unwrap();
+        let mut expected = cards.clone();
+        decoded.sort_by(|a, b| a.card_code.cmp(&b.card_code));
+        expected.sort_by(|a, b| a.card_code.cmp(&b.card_code));
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn varints_round_trip() {
+        for value in [0, 1, 127, 128, 300, 16_383, 16_384, u32::MAX as u64] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+            let mut rest = bytes.as_slice();
+            assert_eq!(read_varint(&mut rest), Ok(value));
+            assert!(rest.is_empty());
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_format_and_version() {
+        assert_eq!(
+            decode(&code_of(&[0x21, 0, 0, 0])),
+            Err(DeckCodeError::InvalidFormat(2))
+        );
+        assert_eq!(
+            decode(&code_of(&[0x16, 0, 0, 0])),
+            Err(DeckCodeError::UnsupportedVersion(6))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_faction() {
+        // One group of one 3-copy card of set 1, faction 8
+        let code = code_of(&[0x15, 1, 1, 1, 8, 1, 0, 0]);
+        assert_eq!(decode(&code), Err(DeckCodeError::UnknownFactionId(8)));
+    }
+
+    #[test]
+    fn rejects_truncated_codes() {
+        assert_eq!(decode(""), Err(DeckCodeError::Truncated));
+        let bytes = base32_decode(REFERENCE_CODES[1]).unwrap();
+        let code = code_of(&bytes[..bytes.len() - 1]);
+        assert_eq!(decode(&code), Err(DeckCodeError::Truncated));
+        // A varint with its continuation bit set but no next byte
+        assert_eq!(
+            decode(&code_of(&[0x11, 0x80])),
+            Err(DeckCodeError::Truncated)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_base32() {
+        assert_eq!(decode("CEBAIAIF!"), Err(DeckCodeError::InvalidBase32));
+        // 0, 1, 8 and 9 are not part of the alphabet
+        assert_eq!(decode("CEBAIAI1"), Err(DeckCodeError::InvalidBase32));
+    }
+
+    #[test]
+    fn rejects_invalid_cards() {
+        assert_eq!(
+            encode(&[card("01XX001", 1)]),
+            Err(DeckCodeError::InvalidCardCode("01XX001".to_string()))
+        );
+        assert_eq!(
+            encode(&[card("01DE01", 1)]),
+            Err(DeckCodeError::InvalidCardCode("01DE01".to_string()))
+        );
+        assert_eq!(
+            encode(&[card("01DE001", 0)]),
+            Err(DeckCodeError::InvalidCount("01DE001".to_string()))
+        );
+    }
+}
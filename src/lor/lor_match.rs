@@ -0,0 +1,91 @@
+use crate::{
+    cache::{self, Endpoint},
+    lor::deck_code::{self, CardCodeAndCount, DeckCodeError},
+    region::routing::RoutingRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LorMatch {
+    pub metadata: LorMetadata,
+    pub info: LorInfo,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LorMetadata {
+    pub data_version: String,
+    pub match_id: String,
+    /// List of participant PUUIDs
+    pub participants: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LorInfo {
+    /// e.g. "Constructed", "Expeditions" or "Tutorial"
+    pub game_mode: String,
+    /// e.g. "Ranked", "Normal", "AI" or "Tutorial"
+    pub game_type: String,
+    pub game_start_time_utc: String,
+    pub game_version: String,
+    pub players: Vec<LorPlayer>,
+    pub total_turn_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LorPlayer {
+    pub puuid: String,
+    pub deck_id: String,
+    pub deck_code: String,
+    pub factions: Vec<String>,
+    pub game_outcome: String,
+    pub order_of_play: i64,
+}
+
+impl LorMatch {
+    pub async fn by_match_id(
+        region: &RoutingRegion,
+        api_key: &String,
+        match_id: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lor/match/v1/matches/{}?api_key={}",
+            region, match_id, api_key
+        );
+        let resp = cache::get(Endpoint::LorMatch, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {:?}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the player's most recent match IDs.
+    pub async fn by_puuid(
+        region: &RoutingRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<Vec<String>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lor/match/v1/matches/by-puuid/{}/ids?api_key={}",
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::LorMatchIds, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {:?}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl LorPlayer {
+    /// Decodes the deck the player used.
+    pub fn cards(&self) -> Result<Vec<CardCodeAndCount>, DeckCodeError> {
+        deck_code::decode(&self.deck_code)
+    }
+}
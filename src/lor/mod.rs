@@ -0,0 +1,5 @@
+pub mod deck;
+pub mod deck_code;
+pub mod lor_match;
+pub mod ranked;
+pub mod status;
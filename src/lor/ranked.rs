@@ -0,0 +1,37 @@
+use crate::{
+    cache::{self, Endpoint},
+    region::routing::RoutingRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Leaderboard {
+    pub players: Vec<LeaderboardPlayer>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LeaderboardPlayer {
+    pub name: String,
+    /// Zero based position on the leaderboard
+    pub rank: i64,
+    pub lp: f64,
+}
+
+impl Leaderboard {
+    /// Requests the Masters leaderboard of a region.
+    pub async fn get(region: &RoutingRegion, api_key: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lor/ranked/v1/leaderboards?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::LorRanked, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use core::fmt;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ddragon::offline::parse_version, lol_match::Match};
+
+/// A game patch such as 14.23, taken from the first two parts of a game version.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Patch {
+    pub major: u64,
+    pub minor: u64,
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl Patch {
+    /// Parses a game version such as "14.23.636.1234" or a Data Dragon version such as "14.23.1".
+    pub fn from_version(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some(Self { major, minor })
+    }
+
+    /// Newest Data Dragon version released for this patch, given versions such as the ones
+    /// returned by `DataDragon::versions` or `directory_versions`.
+    pub fn ddragon_version<'a>(&self, versions: &'a [String]) -> Option<&'a String> {
+        versions
+            .iter()
+            .filter(|version| Self::from_version(version) == Some(*self))
+            .max_by_key(|version| parse_version(version))
+    }
+
+    /// The Data Dragon version most patches are published under, for when no version list
+    /// is available.
+    pub fn default_ddragon_version(&self) -> String {
+        format!("{}.{}.1", self.major, self.minor)
+    }
+}
+
+/// Groups matches by the patch they were played on, matches with an unparseable game
+/// version are dropped.
+pub fn group_by_patch(matches: impl IntoIterator<Item = Match>) -> BTreeMap<Patch, Vec<Match>> {
+    let mut groups: BTreeMap<Patch, Vec<Match>> = BTreeMap::new();
+    for lol_match in matches {
+        if let Some(patch) = lol_match.info.patch() {
+            groups.entry(patch).or_default().push(lol_match);
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn patch(major: u64, minor: u64) -> Patch {
+        Patch { major, minor }
+    }
+
+    fn versions(versions: &[&str]) -> Vec<String> {
+        versions.iter().map(|version| version.to_string()).collect()
+    }
+
+    fn game(match_id: &str, game_version: Option<&str>) -> Match {
+        let mut game = fixtures::game(match_id, 420, 0, vec![]);
+        match game_version {
+            Some(version) => game["info"]["gameVersion"] = version.into(),
+            None => {
+                game["info"].as_object_mut().unwrap().remove("gameVersion");
+            }
+        }
+        fixtures::parse(game)
+    }
+
+    #[test]
+    fn parses_game_and_ddragon_versions() {
+        assert_eq!(Patch::from_version("14.23.636.1234"), Some(patch(14, 23)));
+        assert_eq!(Patch::from_version("14.23.1"), Some(patch(14, 23)));
+        assert_eq!(patch(14, 3).to_string(), "14.3");
+        assert_eq!(patch(14, 3).default_ddragon_version(), "14.3.1");
+        for invalid in ["", "14", "14.", "lolpatch_14.23", "14.x.1", "a.b"] {
+            assert_eq!(Patch::from_version(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn picks_the_newest_ddragon_version() {
+        let versions = versions(&[
+            "14.24.1",
+            "14.23.2",
+            "14.23.10",
+            "14.23.1",
+            "lolpatch_7.20",
+            "lolpatch_14.23",
+            "14.2.1",
+        ]);
+        let version = patch(14, 23).ddragon_version(&versions);
+        assert_eq!(version.map(String::as_str), Some("14.23.10"));
+        assert_eq!(
+            patch(14, 2).ddragon_version(&versions).map(String::as_str),
+            Some("14.2.1")
+        );
+        assert_eq!(patch(7, 20).ddragon_version(&versions), None);
+    }
+
+    #[test]
+    fn groups_matches_by_patch() {
+        let groups = group_by_patch([
+            game("EUW1_1", Some("14.24.640.1")),
+            game("EUW1_2", Some("14.23.636.1234")),
+            game("EUW1_3", Some("14.24.641.2")),
+            game("EUW1_4", Some("unknown")),
+            game("EUW1_5", None),
+        ]);
+        let groups: Vec<(Patch, Vec<&str>)> = groups
+            .iter()
+            .map(|(patch, games)| {
+                let ids = games.iter().map(|game| game.metadata.match_id.as_str());
+                (*patch, ids.collect())
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (patch(14, 23), vec!["EUW1_2"]),
+                (patch(14, 24), vec!["EUW1_1", "EUW1_3"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_game_version_has_no_patch() {
+        let game = game("EUW1_1", None);
+        assert_eq!(game.info.game_version, "");
+        assert_eq!(game.info.patch(), None);
+    }
+}
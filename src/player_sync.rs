@@ -0,0 +1,544 @@
+//! Incremental sync of players' accounts, summoners, matches, timelines and league entries
+//! into a `SyncStorage`.
+
+use core::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::RiotAccount,
+    league::LeagueEntry,
+    lol_match::{Match, Timeline},
+    region::{routing::RoutingRegion, server::ServerRegion},
+    summoner::Summoner,
+};
+
+/// Matches are listed from this many seconds before the previous sync, so games that were
+/// still in progress during it are not missed.
+pub const CHECKPOINT_OVERLAP: u64 = 2 * 60 * 60;
+
+/// Number of match IDs requested per page, the most match-v5 allows.
+const MATCH_IDS_PAGE_SIZE: i64 = 100;
+
+/// A player to sync.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlayerRef {
+    RiotId { game_name: String, tag_line: String },
+    Puuid(String),
+}
+
+impl fmt::Display for PlayerRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RiotId {
+                game_name,
+                tag_line,
+            } => write!(f, "{}#{}", game_name, tag_line),
+            Self::Puuid(puuid) => write!(f, "{}", puuid),
+        }
+    }
+}
+
+/// Progress of a player's last completed sync.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SyncCheckpoint {
+    pub puuid: String,
+    /// Epoch seconds at which the last completed sync started
+    pub synced_at: u64,
+}
+
+/// Storage `PlayerSync` writes to and keeps its checkpoints in.
+pub trait SyncStorage {
+    type Error: fmt::Debug;
+
+    fn checkpoint(&self, puuid: &str) -> Result<Option<SyncCheckpoint>, Self::Error>;
+    fn save_checkpoint(&self, checkpoint: &SyncCheckpoint) -> Result<(), Self::Error>;
+    fn save_account(&self, account: &RiotAccount) -> Result<(), Self::Error>;
+    fn save_summoner(&self, region: &ServerRegion, summoner: &Summoner) -> Result<(), Self::Error>;
+    fn save_league_entries(
+        &self,
+        region: &ServerRegion,
+        puuid: &str,
+        entries: &[LeagueEntry],
+    ) -> Result<(), Self::Error>;
+    fn has_match(&self, match_id: &str) -> Result<bool, Self::Error>;
+    fn save_match(&self, game: &Match) -> Result<(), Self::Error>;
+    fn has_timeline(&self, match_id: &str) -> Result<bool, Self::Error>;
+    /// Saves a timeline body from `Timeline::raw_by_match_id`.
+    fn save_timeline(&self, match_id: &str, body: &str) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+pub enum SyncError<E> {
+    /// The account of the player could not be requested
+    Account(PlayerRef),
+    /// The summoner of the PUUID could not be requested
+    Summoner(String),
+    /// The match IDs of the PUUID could not be requested
+    MatchIds(String),
+    Storage(E),
+}
+
+impl<E: fmt::Debug> fmt::Display for SyncError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Account(player) => write!(f, "could not request the account of {}", player),
+            Self::Summoner(puuid) => write!(f, "could not request the summoner of {}", puuid),
+            Self::MatchIds(puuid) => write!(f, "could not request the match IDs of {}", puuid),
+            Self::Storage(e) => write!(f, "storage error: {:?}", e),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for SyncError<E> {}
+
+/// Outcome of syncing a single player.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SyncReport {
+    pub puuid: String,
+    /// Match IDs listed since the checkpoint, including already stored ones
+    pub listed_matches: usize,
+    pub new_matches: Vec<String>,
+    pub new_timelines: usize,
+    pub league_entries: usize,
+    /// Matches or timelines that could not be requested. The checkpoint is not advanced
+    /// while this is not empty, so the next sync retries them.
+    pub failed: Vec<String>,
+}
+
+/// Syncs players of a single platform into `storage`.
+///
+/// Every run lists the match IDs played since the player's checkpoint and only requests the
+/// matches and timelines the storage does not have yet. The checkpoint is saved once
+/// everything listed is stored, so an interrupted run can be repeated without requesting
+/// the stored matches again.
+pub struct PlayerSync<S: SyncStorage> {
+    pub platform: ServerRegion,
+    /// Region of match-v5
+    pub routing: RoutingRegion,
+    /// Region of account-v1, which does not serve SEA
+    pub account_routing: RoutingRegion,
+    pub api_key: String,
+    pub storage: S,
+    /// Only sync matches of this queue, e.g. 420 for ranked solo
+    pub queue: Option<i64>,
+    /// Epoch seconds to list matches from for players without a checkpoint, None for the
+    /// whole match history
+    pub initial_start_time: Option<u64>,
+    /// Most matches to request per player and run, None for no limit. Remaining matches are
+    /// requested by the next run.
+    pub max_matches: Option<usize>,
+    pub timelines: bool,
+    pub league_entries: bool,
+}
+
+impl<S: SyncStorage> PlayerSync<S> {
+    pub fn new(platform: ServerRegion, api_key: String, storage: S) -> Self {
+        let routing = RoutingRegion::from_server(&platform);
+        let account_routing = match routing {
+            RoutingRegion::SEA => RoutingRegion::ASIA,
+            routing => routing,
+        };
+        Self {
+            platform,
+            routing,
+            account_routing,
+            api_key,
+            storage,
+            queue: None,
+            initial_start_time: None,
+            max_matches: None,
+            timelines: true,
+            league_entries: true,
+        }
+    }
+
+    /// Syncs every player in order, one at a time.
+    pub async fn sync_all(
+        &self,
+        players: &[PlayerRef],
+    ) -> Vec<Result<SyncReport, SyncError<S::Error>>> {
+        let mut reports = Vec::with_capacity(players.len());
+        for player in players {
+            let report = self.sync(player).await;
+            if let Err(e) = &report {
+                error!("{}", e);
+            }
+            reports.push(report);
+        }
+        reports
+    }
+
+    pub async fn sync(&self, player: &PlayerRef) -> Result<SyncReport, SyncError<S::Error>> {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+        let account = self.account(player).await?;
+        let puuid = account.get_puuid().clone();
+        self.storage
+            .save_account(&account)
+            .map_err(SyncError::Storage)?;
+
+        let summoner = Summoner::by_puuid(&self.platform, &self.api_key, &puuid)
+            .await
+            .ok_or_else(|| SyncError::Summoner(puuid.clone()))?;
+        self.storage
+            .save_summoner(&self.platform, &summoner)
+            .map_err(SyncError::Storage)?;
+
+        let mut report = SyncReport {
+            puuid: puuid.clone(),
+            ..Default::default()
+        };
+        let start_time = match self
+            .storage
+            .checkpoint(&puuid)
+            .map_err(SyncError::Storage)?
+        {
+            Some(checkpoint) => Some(checkpoint.synced_at.saturating_sub(CHECKPOINT_OVERLAP)),
+            None => self.initial_start_time,
+        };
+        let match_ids = self.match_ids(&puuid, start_time).await?;
+        report.listed_matches = match_ids.len();
+
+        let mut skipped = false;
+        for match_id in &match_ids {
+            if !self
+                .storage
+                .has_match(match_id)
+                .map_err(SyncError::Storage)?
+            {
+                if self
+                    .max_matches
+                    .is_some_and(|max| report.new_matches.len() >= max)
+                {
+                    skipped = true;
+                    break;
+                }
+                match Match::by_match_id(&self.routing, &self.api_key, match_id).await {
+                    Some(game) => {
+                        self.storage.save_match(&game).map_err(SyncError::Storage)?;
+                        report.new_matches.push(match_id.clone());
+                    }
+                    None => {
+                        report.failed.push(match_id.clone());
+                        continue;
+                    }
+                }
+            }
+            if self.timelines
+                && !self
+                    .storage
+                    .has_timeline(match_id)
+                    .map_err(SyncError::Storage)?
+            {
+                match Timeline::raw_by_match_id(&self.routing, &self.api_key, match_id).await {
+                    Some(body) => {
+                        self.storage
+                            .save_timeline(match_id, &body)
+                            .map_err(SyncError::Storage)?;
+                        report.new_timelines += 1;
+                    }
+                    None => report.failed.push(match_id.clone()),
+                }
+            }
+        }
+
+        if self.league_entries {
+            if let Some(entries) =
+                LeagueEntry::by_puuid(&self.platform, &self.api_key, &puuid).await
+            {
+                self.storage
+                    .save_league_entries(&self.platform, &puuid, &entries)
+                    .map_err(SyncError::Storage)?;
+                report.league_entries = entries.len();
+            }
+        }
+
+        if report.failed.is_empty() && !skipped {
+            self.storage
+                .save_checkpoint(&SyncCheckpoint {
+                    puuid,
+                    synced_at: started_at,
+                })
+                .map_err(SyncError::Storage)?;
+        }
+        info!(
+            "Synced {}, {} new matches, {} failed",
+            player,
+            report.new_matches.len(),
+            report.failed.len()
+        );
+        Ok(report)
+    }
+
+    async fn account(&self, player: &PlayerRef) -> Result<RiotAccount, SyncError<S::Error>> {
+        let account = match player {
+            PlayerRef::RiotId {
+                game_name,
+                tag_line,
+            } => {
+                RiotAccount::by_riot_id(&self.account_routing, &self.api_key, game_name, tag_line)
+                    .await
+            }
+            PlayerRef::Puuid(puuid) => {
+                RiotAccount::by_puuid(&self.account_routing, &self.api_key, puuid).await
+            }
+        };
+        account.ok_or_else(|| SyncError::Account(player.clone()))
+    }
+
+    /// Lists every match ID since `start_time`, oldest first so checkpoints advance in order.
+    async fn match_ids(
+        &self,
+        puuid: &String,
+        start_time: Option<u64>,
+    ) -> Result<Vec<String>, SyncError<S::Error>> {
+        let mut match_ids = Vec::new();
+        loop {
+            let page = Match::by_puuid(
+                &self.routing,
+                &self.api_key,
+                puuid,
+                start_time,
+                None,
+                self.queue,
+                None,
+                Some(match_ids.len() as i64),
+                Some(MATCH_IDS_PAGE_SIZE),
+            )
+            .await
+            .ok_or_else(|| SyncError::MatchIds(puuid.clone()))?;
+            let last_page = (page.len() as i64) < MATCH_IDS_PAGE_SIZE;
+            match_ids.extend(page);
+            if last_page {
+                break;
+            }
+        }
+        match_ids.reverse();
+        Ok(match_ids)
+    }
+}
+
+#[cfg(feature = "storage")]
+impl SyncStorage for crate::storage::SqliteStore {
+    type Error = crate::storage::Error;
+
+    fn checkpoint(&self, puuid: &str) -> Result<Option<SyncCheckpoint>, Self::Error> {
+        self.sync_checkpoint(puuid)
+    }
+
+    fn save_checkpoint(&self, checkpoint: &SyncCheckpoint) -> Result<(), Self::Error> {
+        self.upsert_sync_checkpoint(checkpoint)
+    }
+
+    fn save_account(&self, account: &RiotAccount) -> Result<(), Self::Error> {
+        self.upsert_account(account)
+    }
+
+    fn save_summoner(&self, region: &ServerRegion, summoner: &Summoner) -> Result<(), Self::Error> {
+        self.upsert_summoner(region, summoner)
+    }
+
+    fn save_league_entries(
+        &self,
+        region: &ServerRegion,
+        puuid: &str,
+        entries: &[LeagueEntry],
+    ) -> Result<(), Self::Error> {
+        self.insert_league_entries(region, Some(puuid), entries)
+    }
+
+    fn has_match(&self, match_id: &str) -> Result<bool, Self::Error> {
+        self.has_match(match_id)
+    }
+
+    fn save_match(&self, game: &Match) -> Result<(), Self::Error> {
+        self.upsert_match(game)
+    }
+
+    fn has_timeline(&self, match_id: &str) -> Result<bool, Self::Error> {
+        self.has_timeline(match_id)
+    }
+
+    fn save_timeline(&self, match_id: &str, body: &str) -> Result<(), Self::Error> {
+        self.upsert_timeline(match_id, body)
+    }
+}
+
+#[cfg(all(test, feature = "storage"))]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        cache::{cache_key, Cache, Endpoint, LruCache, ResponseCache},
+        fixtures,
+        storage::SqliteStore,
+    };
+
+    const PUUID: &str = "player0";
+    const SYNCED_AT: u64 = 1_700_000_000;
+    const MATCH_IDS_URL: &str =
+        "https://europe.api.riotgames.com/lol/match/v5/matches/by-puuid/player0/ids";
+
+    /// Responses served from a scoped cache, so a sync run in `Api::scope` sends no request.
+    struct Api {
+        backend: Arc<LruCache>,
+        cache: Cache,
+    }
+
+    impl Api {
+        fn new() -> Self {
+            let backend = Arc::new(LruCache::new(1000));
+            let cache = Cache::new();
+            cache.set_backend(backend.clone());
+            let api = Self { backend, cache };
+            api.serve(
+                Endpoint::Account,
+                "https://europe.api.riotgames.com/riot/account/v1/accounts/by-puuid/player0",
+                r#"{"puuid":"player0","gameName":"player0","tagLine":"EUW"}"#.to_string(),
+            );
+            api.serve(
+                Endpoint::Summoner,
+                "https://euw1.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/player0",
+                r#"{"accountId":"a","profileIconId":1,"revisionDate":0,"id":"s",
+                    "puuid":"player0","summonerLevel":30}"#
+                    .to_string(),
+            );
+            api
+        }
+
+        fn serve(&self, endpoint: Endpoint, url: &str, body: String) {
+            self.backend.insert(cache_key(endpoint, url), body, None);
+        }
+
+        /// Serves the match IDs listed from `start_time`, newest first like match-v5, in
+        /// pages of `MATCH_IDS_PAGE_SIZE`.
+        fn serve_match_ids(&self, start_time: Option<u64>, newest_first: &[String]) {
+            let page_size = MATCH_IDS_PAGE_SIZE as usize;
+            // A final empty page when the last one is full
+            let last_full = newest_first.len().is_multiple_of(page_size);
+            let pages = newest_first
+                .chunks(page_size)
+                .chain(last_full.then_some(&[][..]));
+            for (page, match_ids) in pages.enumerate() {
+                let mut url = format!("{}?start={}", MATCH_IDS_URL, page * page_size);
+                if let Some(start_time) = start_time {
+                    url = format!("{}&startTime={}", url, start_time);
+                }
+                url = format!("{}&count={}", url, MATCH_IDS_PAGE_SIZE);
+                self.serve(
+                    Endpoint::MatchIds,
+                    &url,
+                    serde_json::to_string(match_ids).unwrap(),
+                );
+            }
+        }
+
+        /// Serves a match of the player, or a body that fails to parse if not `valid`.
+        fn serve_match(&self, match_id: &str, valid: bool) {
+            let body = if valid {
+                let player = fixtures::participant(PUUID, 100, "TOP", 1, true);
+                fixtures::game(match_id, 420, 0, vec![player]).to_string()
+            } else {
+                "{}".to_string()
+            };
+            let url = format!(
+                "https://europe.api.riotgames.com/lol/match/v5/matches/{}",
+                match_id
+            );
+            self.serve(Endpoint::Match, &url, body);
+        }
+
+        async fn sync(&self, sync: &PlayerSync<SqliteStore>) -> SyncReport {
+            let player = PlayerRef::Puuid(PUUID.to_string());
+            self.cache.scope(sync.sync(&player)).await.unwrap()
+        }
+    }
+
+    fn player_sync() -> PlayerSync<SqliteStore> {
+        let storage = SqliteStore::open_in_memory().unwrap();
+        let mut sync = PlayerSync::new(ServerRegion::EUW1, "key".to_string(), storage);
+        sync.timelines = false;
+        sync.league_entries = false;
+        sync
+    }
+
+    fn match_ids(range: impl Iterator<Item = u64>) -> Vec<String> {
+        range.map(|n| format!("EUW1_{}", n)).collect()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn save_checkpoint(sync: &PlayerSync<SqliteStore>) {
+        sync.storage
+            .save_checkpoint(&SyncCheckpoint {
+                puuid: PUUID.to_string(),
+                synced_at: SYNCED_AT,
+            })
+            .unwrap();
+    }
+
+    fn checkpoint(sync: &PlayerSync<SqliteStore>) -> Option<u64> {
+        let checkpoint = sync.storage.checkpoint(PUUID).unwrap()?;
+        Some(checkpoint.synced_at)
+    }
+
+    #[tokio::test]
+    async fn pages_are_synced_oldest_first() {
+        let api = Api::new();
+        let newest_first = match_ids((1..=105).rev());
+        api.serve_match_ids(None, &newest_first);
+        for match_id in &newest_first {
+            api.serve_match(match_id, true);
+        }
+        let mut sync = player_sync();
+
+        // Skipped matches keep the checkpoint from being saved
+        sync.max_matches = Some(3);
+        let report = api.sync(&sync).await;
+        assert_eq!(report.listed_matches, 105);
+        assert_eq!(report.new_matches, match_ids(1..=3));
+        assert!(report.failed.is_empty());
+        assert_eq!(checkpoint(&sync), None);
+
+        sync.max_matches = None;
+        let started_at = now();
+        let report = api.sync(&sync).await;
+        assert_eq!(report.new_matches, match_ids(4..=105));
+        let synced_at = checkpoint(&sync).unwrap();
+        assert!((started_at..=now()).contains(&synced_at));
+    }
+
+    #[tokio::test]
+    async fn failed_matches_keep_the_checkpoint() {
+        let api = Api::new();
+        // Listed from two hours before the checkpoint
+        api.serve_match_ids(Some(SYNCED_AT - 2 * 60 * 60), &match_ids((1..=3).rev()));
+        api.serve_match("EUW1_1", true);
+        api.serve_match("EUW1_2", false);
+        api.serve_match("EUW1_3", true);
+        let sync = player_sync();
+        save_checkpoint(&sync);
+
+        let report = api.sync(&sync).await;
+        assert_eq!(report.new_matches, match_ids([1, 3].into_iter()));
+        assert_eq!(report.failed, match_ids(2..=2));
+        assert_eq!(checkpoint(&sync), Some(SYNCED_AT));
+
+        // Once the match is available, only it is requested and the checkpoint advances
+        api.serve_match("EUW1_2", true);
+        let report = api.sync(&sync).await;
+        assert_eq!(report.new_matches, match_ids(2..=2));
+        assert!(checkpoint(&sync).unwrap() > SYNCED_AT);
+    }
+}
@@ -2,3 +2,4 @@ pub mod normal;
 pub mod queue_type;
 pub mod ranked;
 pub mod rgm;
+pub mod tft;
@@ -1,7 +1,9 @@
+use core::fmt;
+
 use log::error;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RankedQueue {
     #[serde(rename = "RANKED_SOLO_5x5")]
     RankedSolo5x5,
@@ -11,12 +13,12 @@ pub enum RankedQueue {
     RankedFlexTT,
 }
 
-impl ToString for RankedQueue {
-    fn to_string(&self) -> String {
+impl fmt::Display for RankedQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
-            Self::RankedSolo5x5 => String::from("RANKED_SOLO_5X5"),
-            Self::RankedFlexSR => String::from("RANKED_FLEX_SR"),
-            Self::RankedFlexTT => String::from("RANKED_FLEX_TT"),
+            Self::RankedSolo5x5 => write!(f, "RANKED_SOLO_5X5"),
+            Self::RankedFlexSR => write!(f, "RANKED_FLEX_SR"),
+            Self::RankedFlexTT => write!(f, "RANKED_FLEX_TT"),
         }
     }
 }
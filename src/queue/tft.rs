@@ -0,0 +1,24 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TftQueue {
+    #[serde(rename = "RANKED_TFT")]
+    RankedTft,
+    /// Hyper roll, ranked by rated tier instead of league tiers
+    #[serde(rename = "RANKED_TFT_TURBO")]
+    RankedTftTurbo,
+    #[serde(rename = "RANKED_TFT_DOUBLE_UP")]
+    RankedTftDoubleUp,
+}
+
+impl fmt::Display for TftQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::RankedTft => write!(f, "RANKED_TFT"),
+            Self::RankedTftTurbo => write!(f, "RANKED_TFT_TURBO"),
+            Self::RankedTftDoubleUp => write!(f, "RANKED_TFT_DOUBLE_UP"),
+        }
+    }
+}
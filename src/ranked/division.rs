@@ -1,7 +1,9 @@
+use core::fmt;
+
 use log::error;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RankedDivision {
     I,
     II,
@@ -9,13 +11,18 @@ pub enum RankedDivision {
     IV,
 }
 
-impl ToString for RankedDivision {
-    fn to_string(&self) -> String {
+impl RankedDivision {
+    /// Every division, from highest to lowest.
+    pub const ALL: [RankedDivision; 4] = [Self::I, Self::II, Self::III, Self::IV];
+}
+
+impl fmt::Display for RankedDivision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
-            RankedDivision::I => String::from("I"),
-            RankedDivision::II => String::from("II"),
-            RankedDivision::III => String::from("III"),
-            RankedDivision::IV => String::from("IV"),
+            RankedDivision::I => write!(f, "I"),
+            RankedDivision::II => write!(f, "II"),
+            RankedDivision::III => write!(f, "III"),
+            RankedDivision::IV => write!(f, "IV"),
         }
     }
 }
@@ -3,7 +3,7 @@ use core::fmt;
 use log::error;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum RankedTier {
     IRON,
     BRONZE,
@@ -34,6 +34,13 @@ impl fmt::Display for RankedTier {
     }
 }
 
+impl RankedTier {
+    /// MASTER, GRANDMASTER and CHALLENGER have a single division and their own league endpoints.
+    pub fn is_apex(&self) -> bool {
+        matches!(self, Self::MASTER | Self::GRANDMASTER | Self::CHALLENGER)
+    }
+}
+
 impl From<&'static str> for RankedTier {
     fn from(value: &'static str) -> Self {
         match value {
@@ -1,8 +1,10 @@
+use core::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use super::server::ServerRegion;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RoutingRegion {
     EUROPE,
     AMERICAS,
@@ -11,14 +13,14 @@ pub enum RoutingRegion {
     ESPORTS,
 }
 
-impl ToString for RoutingRegion {
-    fn to_string(&self) -> String {
+impl fmt::Display for RoutingRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::EUROPE => String::from("europe"),
-            Self::AMERICAS => String::from("americas"),
-            Self::ASIA => String::from("asia"),
-            Self::SEA => String::from("sea"),
-            Self::ESPORTS => String::from("esports"),
+            Self::EUROPE => write!(f, "europe"),
+            Self::AMERICAS => write!(f, "americas"),
+            Self::ASIA => write!(f, "asia"),
+            Self::SEA => write!(f, "sea"),
+            Self::ESPORTS => write!(f, "esports"),
         }
     }
 }
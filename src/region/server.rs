@@ -1,7 +1,9 @@
+use core::fmt;
+
 use log::error;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ServerRegion {
     BR1,
     EUN1,
@@ -22,26 +24,49 @@ pub enum ServerRegion {
     VN2,
 }
 
-impl ToString for ServerRegion {
-    fn to_string(&self) -> String {
+impl ServerRegion {
+    /// Every platform region, in declaration order.
+    pub const ALL: [ServerRegion; 17] = [
+        Self::BR1,
+        Self::EUN1,
+        Self::EUW1,
+        Self::JP1,
+        Self::KR,
+        Self::LA1,
+        Self::LA2,
+        Self::ME1,
+        Self::NA1,
+        Self::OC1,
+        Self::PH2,
+        Self::RU,
+        Self::SG2,
+        Self::TH2,
+        Self::TR1,
+        Self::TW2,
+        Self::VN2,
+    ];
+}
+
+impl fmt::Display for ServerRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
-            Self::BR1 => String::from("br1"),
-            Self::EUN1 => String::from("eun1"),
-            Self::EUW1 => String::from("euw1"),
-            Self::JP1 => String::from("jp1"),
-            Self::KR => String::from("kr"),
-            Self::LA1 => String::from("la1"),
-            Self::LA2 => String::from("la2"),
-            Self::ME1 => String::from("me1"),
-            Self::NA1 => String::from("na1"),
-            Self::OC1 => String::from("oc1"),
-            Self::PH2 => String::from("ph2"),
-            Self::RU => String::from("ru"),
-            Self::SG2 => String::from("sg2"),
-            Self::TH2 => String::from("th2"),
-            Self::TR1 => String::from("tr1"),
-            Self::TW2 => String::from("tw2"),
-            Self::VN2 => String::from("vn2"),
+            Self::BR1 => write!(f, "br1"),
+            Self::EUN1 => write!(f, "eun1"),
+            Self::EUW1 => write!(f, "euw1"),
+            Self::JP1 => write!(f, "jp1"),
+            Self::KR => write!(f, "kr"),
+            Self::LA1 => write!(f, "la1"),
+            Self::LA2 => write!(f, "la2"),
+            Self::ME1 => write!(f, "me1"),
+            Self::NA1 => write!(f, "na1"),
+            Self::OC1 => write!(f, "oc1"),
+            Self::PH2 => write!(f, "ph2"),
+            Self::RU => write!(f, "ru"),
+            Self::SG2 => write!(f, "sg2"),
+            Self::TH2 => write!(f, "th2"),
+            Self::TR1 => write!(f, "tr1"),
+            Self::TW2 => write!(f, "tw2"),
+            Self::VN2 => write!(f, "vn2"),
         }
     }
 }
@@ -0,0 +1,198 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    cache::{self, Endpoint},
+    champion::ChampionId,
+    league::LeagueEntry,
+    region::server::ServerRegion,
+};
+use log::error;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// Result of looking up a player's live game.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum ActiveGame {
+    InGame(CurrentGame),
+    /// The API responded with 404, the player is not currently in a game
+    NotInGame,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentGame {
+    pub game_id: u64,
+    pub game_type: String,
+    /// Unix timestamp in milliseconds, 0 while the game is still loading
+    pub game_start_time: u64,
+    pub map_id: i64,
+    /// Seconds since the game started, as of the request
+    pub game_length: i64,
+    pub platform_id: String,
+    pub game_mode: String,
+    pub banned_champions: Vec<BannedChampion>,
+    pub game_queue_config_id: Option<i64>,
+    pub observers: Observer,
+    pub participants: Vec<CurrentGameParticipant>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BannedChampion {
+    pub pick_turn: i64,
+    /// -1 if no champion was banned
+    pub champion_id: ChampionId,
+    pub team_id: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Observer {
+    /// Key used to decrypt the spectator grid game data for playback
+    pub encryption_key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentGameParticipant {
+    pub champion_id: ChampionId,
+    pub perks: Option<Perks>,
+    pub profile_icon_id: i64,
+    pub bot: bool,
+    pub team_id: i64,
+    pub summoner_id: Option<String>,
+    /// None for bots
+    pub puuid: Option<String>,
+    pub riot_id: Option<String>,
+    pub spell1_id: i64,
+    pub spell2_id: i64,
+    #[serde(default)]
+    pub game_customization_objects: Vec<GameCustomizationObject>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Perks {
+    pub perk_ids: Vec<i64>,
+    pub perk_style: i64,
+    pub perk_sub_style: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameCustomizationObject {
+    pub category: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FeaturedGames {
+    pub game_list: Vec<FeaturedGame>,
+    /// Suggested number of seconds to wait before requesting featured games again
+    pub client_refresh_interval: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FeaturedGame {
+    pub game_mode: String,
+    pub game_length: i64,
+    pub map_id: i64,
+    pub game_type: String,
+    pub banned_champions: Vec<BannedChampion>,
+    pub game_id: u64,
+    pub observers: Observer,
+    pub game_queue_config_id: Option<i64>,
+    pub participants: Vec<FeaturedGameParticipant>,
+    pub platform_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FeaturedGameParticipant {
+    pub bot: bool,
+    pub spell2_id: i64,
+    pub profile_icon_id: i64,
+    pub puuid: Option<String>,
+    pub riot_id: Option<String>,
+    pub champion_id: ChampionId,
+    pub team_id: i64,
+    pub spell1_id: i64,
+}
+
+impl CurrentGame {
+    /// Requests the live game of the given player.
+    ///
+    /// Returns `ActiveGame::NotInGame` if the player is not in a game and None on error.
+    pub async fn by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<ActiveGame> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/spectator/v5/active-games/by-summoner/{}?api_key={}",
+            region, puuid, api_key
+        );
+        let (status, resp) = cache::get_with_status(Endpoint::ActiveGame, request_url).await;
+        if status == StatusCode::NOT_FOUND {
+            return Some(ActiveGame::NotInGame);
+        }
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(ActiveGame::InGame(t)),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Time since the game started, zero while the game is still loading.
+    pub fn elapsed(&self) -> Duration {
+        if self.game_start_time == 0 {
+            return Duration::ZERO;
+        }
+        let start = UNIX_EPOCH + Duration::from_millis(self.game_start_time);
+        SystemTime::now()
+            .duration_since(start)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Requests the ranked entries of every participant, in participant order.
+    ///
+    /// Bots and participants without a PUUID, or whose lookup failed, are paired with None.
+    pub async fn participant_ranks(
+        &self,
+        region: &ServerRegion,
+        api_key: &String,
+    ) -> Vec<(&CurrentGameParticipant, Option<Vec<LeagueEntry>>)> {
+        let mut ranks = Vec::with_capacity(self.participants.len());
+        for participant in &self.participants {
+            let entries = match &participant.puuid {
+                Some(puuid) if !participant.bot => {
+                    LeagueEntry::by_puuid(region, api_key, puuid).await
+                }
+                _ => None,
+            };
+            ranks.push((participant, entries));
+        }
+        ranks
+    }
+}
+
+impl FeaturedGames {
+    pub async fn get(region: &ServerRegion, api_key: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/spectator/v5/featured-games?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::FeaturedGames, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    hash::Hash,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{lol_match::Match, patch::Patch, ranked::tier::RankedTier};
+
+use super::{rate, Breakdown, Interval, MatchGroup, Record, Z_95};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct GroupCounts {
+    matches: u64,
+    /// Matches each champion was banned in
+    bans: HashMap<i64, u64>,
+    #[serde(with = "entries")]
+    picks: HashMap<(i64, Option<String>), Record>,
+}
+
+/// Per-champion games, wins, picks and bans of a corpus of matches.
+///
+/// Matches are added one at a time along with the tier bucket they were sampled from, and
+/// `rows` computes the rates of every champion and group. Counts are plain sums, so
+/// aggregators of separate shards of a corpus can be serialized and `merge`d.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChampionAggregator {
+    pub breakdown: Breakdown,
+    /// Rows with fewer games are flagged as low sample
+    pub min_games: u64,
+    /// z-score of the confidence intervals
+    pub z: f64,
+    /// Leave remade games out
+    pub skip_remakes: bool,
+    #[serde(with = "entries")]
+    groups: HashMap<MatchGroup, GroupCounts>,
+    champion_names: HashMap<i64, String>,
+}
+
+/// Statistics of a champion within a group of matches.
+///
+/// Bans are counted per champion, so every position row of a champion has the same ban
+/// rate. Champions that were banned but never picked have a row without a position.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChampionRow {
+    pub champion_id: i64,
+    /// Empty for champions that were only banned
+    pub champion_name: String,
+    pub position: Option<String>,
+    pub queue_id: Option<i64>,
+    pub patch: Option<Patch>,
+    pub tier: Option<RankedTier>,
+    /// Matches in the group
+    pub matches: u64,
+    pub games: u64,
+    pub wins: u64,
+    pub bans: u64,
+    pub win_rate: f64,
+    pub win_rate_interval: Interval,
+    pub pick_rate: f64,
+    pub pick_rate_interval: Interval,
+    pub ban_rate: f64,
+    pub ban_rate_interval: Interval,
+    /// Fewer games than `ChampionAggregator::min_games`
+    pub low_sample: bool,
+}
+
+impl Default for ChampionAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChampionAggregator {
+    /// Breaks down by every dimension, with 95% intervals and a low sample below 100 games.
+    pub fn new() -> Self {
+        Self {
+            breakdown: Breakdown::default(),
+            min_games: 100,
+            z: Z_95,
+            skip_remakes: true,
+            groups: HashMap::new(),
+            champion_names: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, game: &Match, tier: Option<RankedTier>) {
+        if self.skip_remakes && game.info.is_remake() {
+            return;
+        }
+        let counts = self
+            .groups
+            .entry(self.breakdown.group(game, tier))
+            .or_default();
+        counts.matches += 1;
+
+        // Both teams may ban the same champion
+        let banned: BTreeSet<i64> = game
+            .info
+            .teams
+            .iter()
+            .flat_map(|team| team.get_bans())
+            .map(|ban| ban.get_champion_id())
+            .filter(|id| *id != -1)
+            .collect();
+        for champion_id in banned {
+            *counts.bans.entry(champion_id).or_default() += 1;
+        }
+
+        for participant in &game.info.participants {
+            let champion_id = participant.get_champion_id();
+            let position = self.breakdown.position(participant.get_team_position());
+            counts
+                .picks
+                .entry((champion_id, position))
+                .or_default()
+                .add(participant.get_win());
+            self.champion_names
+                .entry(champion_id)
+                .or_insert_with(|| participant.get_champion_name().clone());
+        }
+    }
+
+    /// Adds every match, all sampled from the same tier bucket.
+    pub fn extend<'a>(
+        &mut self,
+        games: impl IntoIterator<Item = &'a Match>,
+        tier: Option<RankedTier>,
+    ) {
+        for game in games {
+            self.add(game, tier);
+        }
+    }
+
+    /// Adds the counts of another shard, which should use the same `breakdown`.
+    pub fn merge(&mut self, other: ChampionAggregator) {
+        for (group, other) in other.groups {
+            let counts = self.groups.entry(group).or_default();
+            counts.matches += other.matches;
+            for (champion_id, bans) in other.bans {
+                *counts.bans.entry(champion_id).or_default() += bans;
+            }
+            for (pick, record) in other.picks {
+                counts.picks.entry(pick).or_default().merge(record);
+            }
+        }
+        for (champion_id, name) in other.champion_names {
+            self.champion_names.entry(champion_id).or_insert(name);
+        }
+    }
+
+    /// Number of matches counted.
+    pub fn matches(&self) -> u64 {
+        self.groups.values().map(|counts| counts.matches).sum()
+    }
+
+    /// Rows of every champion, ordered by group, then position, then most games.
+    pub fn rows(&self) -> Vec<ChampionRow> {
+        let mut rows = Vec::new();
+        for (group, counts) in &self.groups {
+            for ((champion_id, position), picks) in &counts.picks {
+                rows.push(self.row(group, counts, *champion_id, position.clone(), picks));
+            }
+            let picked: HashSet<i64> = counts.picks.keys().map(|(id, _)| *id).collect();
+            for champion_id in counts.bans.keys() {
+                if !picked.contains(champion_id) {
+                    rows.push(self.row(group, counts, *champion_id, None, &Record::default()));
+                }
+            }
+        }
+        rows.sort_by(|a, b| {
+            (&a.queue_id, &a.patch, &a.tier, &a.position)
+                .cmp(&(&b.queue_id, &b.patch, &b.tier, &b.position))
+                .then(b.games.cmp(&a.games))
+                .then(a.champion_id.cmp(&b.champion_id))
+        });
+        rows
+    }
+
+    fn row(
+        &self,
+        group: &MatchGroup,
+        counts: &GroupCounts,
+        champion_id: i64,
+        position: Option<String>,
+        picks: &Record,
+    ) -> ChampionRow {
+        let bans = counts.bans.get(&champion_id).copied().unwrap_or(0);
+        ChampionRow {
+            champion_id,
+            champion_name: self
+                .champion_names
+                .get(&champion_id)
+                .cloned()
+                .unwrap_or_default(),
+            position,
+            queue_id: group.queue_id,
+            patch: group.patch,
+            tier: group.tier,
+            matches: counts.matches,
+            games: picks.games,
+            wins: picks.wins,
+            bans,
+            win_rate: picks.win_rate(),
+            win_rate_interval: picks.win_rate_interval(self.z),
+            pick_rate: rate(picks.games, counts.matches),
+            pick_rate_interval: Interval::wilson(picks.games, counts.matches, self.z),
+            ban_rate: rate(bans, counts.matches),
+            ban_rate_interval: Interval::wilson(bans, counts.matches, self.z),
+            low_sample: picks.games < self.min_games,
+        }
+    }
+}
+
+/// Maps with keys JSON has no representation for, serialized as lists of entries.
+mod entries {
+    use super::*;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn row(rows: &[ChampionRow], champion_id: i64) -> &ChampionRow {
+        rows.iter()
+            .find(|row| row.champion_id == champion_id)
+            .unwrap()
+    }
+
+    fn rows_json(aggregator: &ChampionAggregator) -> serde_json::Value {
+        serde_json::to_value(aggregator.rows()).unwrap()
+    }
+
+    #[test]
+    fn bans_are_counted_once_per_match() {
+        let mut game = fixtures::game(
+            "EUW1_1",
+            420,
+            0,
+            vec![
+                fixtures::participant("a", 100, "TOP", 1, true),
+                fixtures::participant("b", 200, "TOP", 2, false),
+            ],
+        );
+        // Both teams banning a champion, and a skipped ban
+        fixtures::ban(&mut game, 100, 5);
+        fixtures::ban(&mut game, 200, 5);
+        fixtures::ban(&mut game, 200, 1);
+        fixtures::ban(&mut game, 100, -1);
+        let mut aggregator = ChampionAggregator::new();
+        aggregator.add(&fixtures::parse(game), Some(RankedTier::GOLD));
+
+        let rows = aggregator.rows();
+        assert_eq!(rows.len(), 3);
+        let banned = row(&rows, 5);
+        assert_eq!((banned.bans, banned.games, banned.ban_rate), (1, 0, 1.0));
+        assert_eq!(banned.position, None);
+        let picked = row(&rows, 1);
+        assert_eq!((picked.bans, picked.games, picked.wins), (1, 1, 1));
+        assert_eq!(picked.position.as_deref(), Some("TOP"));
+        assert_eq!(picked.tier, Some(RankedTier::GOLD));
+        assert_eq!(picked.queue_id, Some(420));
+    }
+
+    #[test]
+    fn participants_without_position_have_none() {
+        let game = fixtures::game(
+            "EUW1_1",
+            450,
+            0,
+            vec![
+                fixtures::participant("a", 100, "", 1, true),
+                fixtures::participant("b", 200, "", 2, false),
+            ],
+        );
+        let mut aggregator = ChampionAggregator::new();
+        aggregator.add(&fixtures::parse(game), None);
+        assert!(aggregator.rows().iter().all(|row| row.position.is_none()));
+    }
+
+    #[test]
+    fn merged_shards_match_a_single_pass() {
+        let games: Vec<Match> = (1..=4)
+            .map(|n| fixtures::ranked_game(&format!("EUW1_{}", n), n * 1000))
+            .collect();
+        let mut single = ChampionAggregator::new();
+        single.extend(&games, Some(RankedTier::GOLD));
+
+        let mut first = ChampionAggregator::new();
+        first.extend(&games[..1], Some(RankedTier::GOLD));
+        let mut second = ChampionAggregator::new();
+        second.extend(&games[1..], Some(RankedTier::GOLD));
+        // Shards are serialized between workers
+        let json = serde_json::to_string(&second).unwrap();
+        first.merge(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(first.matches(), 4);
+        assert_eq!(rows_json(&first), rows_json(&single));
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::lol_match::{Match, Participant};
+
+use super::{Interval, Record};
+
+/// Records of champions against or alongside other champions, by the champion ID of each side.
+pub type PairRecords = BTreeMap<i64, BTreeMap<i64, Record>>;
+
+/// Lane matchups and duo synergies of a corpus of matches.
+///
+/// Lane matchups are recorded from both sides, so `matchups[position][a][b]` has the games
+/// of `matchups[position][b][a]` with wins and losses swapped. Matches are not broken down by
+/// queue or patch, shard them with `patch::group_by_patch` first to compare patches. Remakes
+/// are left out.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MatchupStats {
+    /// Positions whose champions are paired as duos, e.g. ["BOTTOM", "UTILITY"]
+    pub duo_positions: Vec<[String; 2]>,
+    pub matches: u64,
+    /// Records of a champion against the opposing champion of the same `team_position`,
+    /// by position
+    pub matchups: BTreeMap<String, PairRecords>,
+    /// Records of the champion of the first position with the champion of the second one on
+    /// the same team, by "FIRST+SECOND" positions
+    pub duos: BTreeMap<String, PairRecords>,
+}
+
+/// Pair records laid out as a matrix, e.g. to feed a draft tool.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Matrix {
+    /// Champion IDs of the rows, ascending
+    pub rows: Vec<i64>,
+    /// Champion IDs of the columns, ascending
+    pub columns: Vec<i64>,
+    /// `games[row][column]`
+    pub games: Vec<Vec<u64>>,
+    /// Wins of the row champion
+    pub wins: Vec<Vec<u64>>,
+    /// Win rates of the row champion, None for pairs without games or with fewer than
+    /// `min_games`
+    pub win_rates: Vec<Vec<Option<f64>>>,
+    pub win_rate_intervals: Vec<Vec<Option<Interval>>>,
+}
+
+impl Default for MatchupStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatchupStats {
+    /// Pairs bot lane with support and jungle with mid lane.
+    pub fn new() -> Self {
+        Self::with_duos(vec![
+            ["BOTTOM".to_string(), "UTILITY".to_string()],
+            ["JUNGLE".to_string(), "MIDDLE".to_string()],
+        ])
+    }
+
+    pub fn with_duos(duo_positions: Vec<[String; 2]>) -> Self {
+        Self {
+            duo_positions,
+            matches: 0,
+            matchups: BTreeMap::new(),
+            duos: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, game: &Match) {
+        if game.info.is_remake() {
+            return;
+        }
+        self.matches += 1;
+
+        let mut teams: BTreeMap<i64, BTreeMap<&str, Vec<&Participant>>> = BTreeMap::new();
+        for participant in &game.info.participants {
+            if participant.get_team_position().is_empty() {
+                continue;
+            }
+            teams
+                .entry(participant.get_team_id())
+                .or_default()
+                .entry(participant.get_team_position())
+                .or_default()
+                .push(participant);
+        }
+
+        let team_ids: Vec<&i64> = teams.keys().collect();
+        if let [blue, red] = team_ids[..] {
+            let positions: BTreeSet<&str> = teams
+                .values()
+                .flat_map(|team| team.keys())
+                .copied()
+                .collect();
+            for position in positions {
+                let (Some(a), Some(b)) = (
+                    single(&teams[blue], position),
+                    single(&teams[red], position),
+                ) else {
+                    continue;
+                };
+                let matchups = self.matchups.entry(position.to_string()).or_default();
+                add_pair(matchups, a, b);
+                add_pair(matchups, b, a);
+            }
+        }
+
+        for [first, second] in &self.duo_positions {
+            let duos = self
+                .duos
+                .entry(format!("{}+{}", first, second))
+                .or_default();
+            for team in teams.values() {
+                if let (Some(a), Some(b)) = (single(team, first), single(team, second)) {
+                    add_pair(duos, a, b);
+                }
+            }
+        }
+    }
+
+    pub fn extend<'a>(&mut self, games: impl IntoIterator<Item = &'a Match>) {
+        for game in games {
+            self.add(game);
+        }
+    }
+
+    /// Adds the counts of another shard. Duo positions missing from this one are added.
+    pub fn merge(&mut self, other: MatchupStats) {
+        self.matches += other.matches;
+        for duo in other.duo_positions {
+            if !self.duo_positions.contains(&duo) {
+                self.duo_positions.push(duo);
+            }
+        }
+        for (position, records) in other.matchups {
+            merge_pairs(self.matchups.entry(position).or_default(), records);
+        }
+        for (positions, records) in other.duos {
+            merge_pairs(self.duos.entry(positions).or_default(), records);
+        }
+    }
+
+    /// Record of `champion_id` against `opponent_id` in `position`.
+    pub fn matchup(&self, position: &str, champion_id: i64, opponent_id: i64) -> Record {
+        pair_record(self.matchups.get(position), champion_id, opponent_id)
+    }
+
+    /// Record of `first_id` in the first position alongside `second_id` in the second one.
+    pub fn duo(&self, first: &str, second: &str, first_id: i64, second_id: i64) -> Record {
+        let duos = self.duos.get(&format!("{}+{}", first, second));
+        pair_record(duos, first_id, second_id)
+    }
+
+    /// Matchups of a position, rows and columns holding the same champions. Pairs with fewer
+    /// than `min_games` games have no win rate.
+    pub fn matchup_matrix(&self, position: &str, min_games: u64, z: f64) -> Option<Matrix> {
+        Some(Matrix::new(self.matchups.get(position)?, min_games, z))
+    }
+
+    /// Duos of the first position, as rows, with the second one, as columns. Pairs with fewer
+    /// than `min_games` games have no win rate.
+    pub fn duo_matrix(&self, first: &str, second: &str, min_games: u64, z: f64) -> Option<Matrix> {
+        let duos = self.duos.get(&format!("{}+{}", first, second))?;
+        Some(Matrix::new(duos, min_games, z))
+    }
+}
+
+impl Matrix {
+    /// Lays out `records`, keeping the games and wins of every pair but only computing the
+    /// win rates of pairs with at least `min_games` games.
+    pub fn new(records: &PairRecords, min_games: u64, z: f64) -> Self {
+        let rows: Vec<i64> = records.keys().copied().collect();
+        let columns: Vec<i64> = records
+            .values()
+            .flat_map(|row| row.keys().copied())
+            .collect::<BTreeSet<i64>>()
+            .into_iter()
+            .collect();
+        let mut matrix = Self {
+            games: Vec::with_capacity(rows.len()),
+            wins: Vec::with_capacity(rows.len()),
+            win_rates: Vec::with_capacity(rows.len()),
+            win_rate_intervals: Vec::with_capacity(rows.len()),
+            rows,
+            columns,
+        };
+        for row in records.values() {
+            let cells: Vec<Record> = matrix
+                .columns
+                .iter()
+                .map(|column| row.get(column).copied().unwrap_or_default())
+                .collect();
+            let games = cells.iter().map(|record| record.games);
+            let wins = cells.iter().map(|record| record.wins);
+            let rated: Vec<Option<&Record>> = cells
+                .iter()
+                .map(|record| (record.games > 0 && record.games >= min_games).then_some(record))
+                .collect();
+            let win_rates = rated.iter().map(|cell| cell.map(Record::win_rate));
+            let intervals = rated
+                .iter()
+                .map(|cell| cell.map(|record| record.win_rate_interval(z)));
+            matrix.games.push(games.collect());
+            matrix.wins.push(wins.collect());
+            matrix.win_rates.push(win_rates.collect());
+            matrix.win_rate_intervals.push(intervals.collect());
+        }
+        matrix
+    }
+}
+
+/// The player of a team in a position, None if the team has none or several there.
+fn single<'a>(
+    team: &BTreeMap<&str, Vec<&'a Participant>>,
+    position: &str,
+) -> Option<&'a Participant> {
+    match team.get(position).map(Vec::as_slice) {
+        Some([participant]) => Some(participant),
+        _ => None,
+    }
+}
+
+fn add_pair(records: &mut PairRecords, a: &Participant, b: &Participant) {
+    records
+        .entry(a.get_champion_id())
+        .or_default()
+        .entry(b.get_champion_id())
+        .or_default()
+        .add(a.get_win());
+}
+
+fn merge_pairs(records: &mut PairRecords, other: PairRecords) {
+    for (champion_id, row) in other {
+        let merged = records.entry(champion_id).or_default();
+        for (other_id, record) in row {
+            merged.entry(other_id).or_default().merge(record);
+        }
+    }
+}
+
+fn pair_record(records: Option<&PairRecords>, a: i64, b: i64) -> Record {
+    records
+        .and_then(|records| records.get(&a)?.get(&b))
+        .copied()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, stats::Z_95};
+
+    /// Champions 0 to 4 on blue side, 5 to 9 on red side, see `fixtures::ranked_game`.
+    fn games() -> Vec<Match> {
+        let mut games: Vec<Match> = (1..=3)
+            .map(|n| fixtures::ranked_game(&format!("EUW1_{}", n), n * 1000))
+            .collect();
+        // Red side winning, with champion 10 as support
+        let positions = ["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY"];
+        let participants = (0..10)
+            .map(|n| {
+                let team_id = if n < 5 { 100 } else { 200 };
+                let champion_id = if n == 9 { 10 } else { n as i64 };
+                let puuid = format!("player{}", n);
+                fixtures::participant(&puuid, team_id, positions[n % 5], champion_id, n >= 5)
+            })
+            .collect();
+        games.push(fixtures::parse(fixtures::game(
+            "EUW1_4",
+            420,
+            4000,
+            participants,
+        )));
+        games
+    }
+
+    #[test]
+    fn matchups_are_symmetric() {
+        let mut stats = MatchupStats::new();
+        stats.extend(&games());
+        assert_eq!(stats.matches, 4);
+        assert_eq!(stats.matchup("TOP", 0, 5), Record { games: 4, wins: 3 });
+        assert_eq!(stats.matchup("TOP", 5, 0), Record { games: 4, wins: 1 });
+        assert_eq!(stats.matchup("TOP", 0, 6), Record::default());
+        for (position, records) in &stats.matchups {
+            for (champion_id, row) in records {
+                for (opponent_id, record) in row {
+                    let mirrored = stats.matchup(position, *opponent_id, *champion_id);
+                    assert_eq!(mirrored.games, record.games);
+                    assert_eq!(mirrored.wins, record.games - record.wins);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn duos_pair_positions_of_the_same_team() {
+        let mut stats = MatchupStats::new();
+        stats.extend(&games());
+        assert_eq!(
+            stats.duo("BOTTOM", "UTILITY", 3, 4),
+            Record { games: 4, wins: 3 }
+        );
+        assert_eq!(
+            stats.duo("BOTTOM", "UTILITY", 8, 9),
+            Record { games: 3, wins: 0 }
+        );
+        assert_eq!(
+            stats.duo("BOTTOM", "UTILITY", 8, 10),
+            Record { games: 1, wins: 1 }
+        );
+        assert_eq!(stats.duo("JUNGLE", "MIDDLE", 1, 2).games, 4);
+        // Never across teams or in the reverse order
+        assert_eq!(stats.duo("BOTTOM", "UTILITY", 3, 9), Record::default());
+        assert_eq!(stats.duo("UTILITY", "BOTTOM", 4, 3), Record::default());
+    }
+
+    #[test]
+    fn merged_shards_match_a_single_pass() {
+        let games = games();
+        let mut single = MatchupStats::new();
+        single.extend(&games);
+
+        let mut first = MatchupStats::new();
+        first.extend(&games[..2]);
+        let mut second = MatchupStats::with_duos(vec![["TOP".to_string(), "JUNGLE".to_string()]]);
+        second
+            .duo_positions
+            .extend(MatchupStats::new().duo_positions);
+        second.extend(&games[2..]);
+        let json = serde_json::to_string(&second).unwrap();
+        first.merge(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(first.matches, single.matches);
+        assert_eq!(first.matchups, single.matchups);
+        assert_eq!(first.duos["BOTTOM+UTILITY"], single.duos["BOTTOM+UTILITY"]);
+        assert_eq!(first.duos["JUNGLE+MIDDLE"], single.duos["JUNGLE+MIDDLE"]);
+        assert_eq!(first.duo_positions.len(), 3);
+    }
+
+    #[test]
+    fn matrices_keep_counts_below_min_games() {
+        let mut stats = MatchupStats::new();
+        stats.extend(&games());
+        let matrix = stats.duo_matrix("BOTTOM", "UTILITY", 2, Z_95).unwrap();
+        assert_eq!(matrix.rows, vec![3, 8]);
+        assert_eq!(matrix.columns, vec![4, 9, 10]);
+        assert_eq!(matrix.games, vec![vec![4, 0, 0], vec![0, 3, 1]]);
+        assert_eq!(matrix.wins, vec![vec![3, 0, 0], vec![0, 0, 1]]);
+        assert_eq!(
+            matrix.win_rates,
+            vec![vec![Some(0.75), None, None], vec![None, Some(0.0), None]]
+        );
+        assert_eq!(
+            matrix.win_rate_intervals[0][0],
+            Some(Interval::wilson(3, 4, Z_95))
+        );
+        assert_eq!(matrix.win_rate_intervals[1][2], None);
+        assert!(stats.matchup_matrix("NONE", 0, Z_95).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+//! Statistics aggregated over a corpus of matches, e.g. the matches of a `LadderCrawler`.
+
+pub mod champion;
+pub mod matchup;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{lol_match::Match, patch::Patch, ranked::tier::RankedTier};
+
+/// z-score of a two-sided 95% confidence interval.
+pub const Z_95: f64 = 1.959964;
+
+/// Confidence interval of a proportion, both bounds within [0, 1].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl Interval {
+    /// Wilson score interval of `successes` out of `trials`. Unlike the normal approximation
+    /// it stays meaningful for small samples and rates close to 0 or 1.
+    ///
+    /// Returns [0, 1] without trials.
+    pub fn wilson(successes: u64, trials: u64, z: f64) -> Self {
+        if trials == 0 {
+            return Self {
+                lower: 0.0,
+                upper: 1.0,
+            };
+        }
+        let n = trials as f64;
+        let p = successes as f64 / n;
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        Self {
+            lower: (center - margin).max(0.0),
+            upper: (center + margin).min(1.0),
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+}
+
+/// Games and wins of a champion, or of a pair of champions.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub games: u64,
+    pub wins: u64,
+}
+
+impl Record {
+    pub fn add(&mut self, win: bool) {
+        self.games += 1;
+        if win {
+            self.wins += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: Record) {
+        self.games += other.games;
+        self.wins += other.wins;
+    }
+
+    /// 0 without games.
+    pub fn win_rate(&self) -> f64 {
+        rate(self.wins, self.games)
+    }
+
+    pub fn win_rate_interval(&self, z: f64) -> Interval {
+        Interval::wilson(self.wins, self.games, z)
+    }
+}
+
+/// Dimensions statistics are broken down by. Disabled dimensions are None in the results.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Breakdown {
+    /// By `team_position`
+    pub position: bool,
+    pub queue: bool,
+    pub patch: bool,
+    /// By the tier given with each match
+    pub tier: bool,
+}
+
+impl Default for Breakdown {
+    fn default() -> Self {
+        Self {
+            position: true,
+            queue: true,
+            patch: true,
+            tier: true,
+        }
+    }
+}
+
+impl Breakdown {
+    /// Group a match is counted in.
+    pub fn group(&self, game: &Match, tier: Option<RankedTier>) -> MatchGroup {
+        MatchGroup {
+            queue_id: self.queue.then_some(game.info.queue_id),
+            patch: if self.patch { game.info.patch() } else { None },
+            tier: if self.tier { tier } else { None },
+        }
+    }
+
+    /// Position a participant is counted in, None if positions are not broken down by or the
+    /// participant has none, e.g. in ARAM.
+    pub fn position(&self, team_position: &str) -> Option<String> {
+        (self.position && !team_position.is_empty()).then(|| team_position.to_string())
+    }
+}
+
+/// Matches sharing a queue, patch and tier bucket.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MatchGroup {
+    pub queue_id: Option<i64>,
+    pub patch: Option<Patch>,
+    /// Tier bucket the match was sampled from, e.g. the tier of the ladder a crawler read
+    pub tier: Option<RankedTier>,
+}
+
+/// `count / total`, 0 without a total.
+pub(crate) fn rate(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_interval(interval: Interval, lower: f64, upper: f64) {
+        assert!(
+            (interval.lower - lower).abs() < 1e-4 && (interval.upper - upper).abs() < 1e-4,
+            "{:?} is not [{}, {}]",
+            interval,
+            lower,
+            upper
+        );
+    }
+
+    #[test]
+    fn wilson_intervals() {
+        assert_interval(Interval::wilson(50, 100, Z_95), 0.4038, 0.5962);
+        assert_interval(Interval::wilson(0, 10, Z_95), 0.0, 0.2775);
+        assert_interval(Interval::wilson(10, 10, Z_95), 0.7225, 1.0);
+        assert_interval(Interval::wilson(0, 0, Z_95), 0.0, 1.0);
+        // Narrower with more trials
+        let small = Interval::wilson(5, 10, Z_95);
+        let large = Interval::wilson(500, 1000, Z_95);
+        assert!(large.width() < small.width());
+    }
+
+    #[test]
+    fn records_and_rates() {
+        let mut record = Record::default();
+        assert_eq!(record.win_rate(), 0.0);
+        record.add(true);
+        record.add(false);
+        record.merge(Record { games: 2, wins: 2 });
+        assert_eq!(record, Record { games: 4, wins: 3 });
+        assert_eq!(record.win_rate(), 0.75);
+    }
+
+    #[test]
+    fn empty_positions_are_none() {
+        let breakdown = Breakdown::default();
+        assert_eq!(breakdown.position("MIDDLE").as_deref(), Some("MIDDLE"));
+        assert_eq!(breakdown.position(""), None);
+        let breakdown = Breakdown {
+            position: false,
+            ..Breakdown::default()
+        };
+        assert_eq!(breakdown.position("MIDDLE"), None);
+    }
+}
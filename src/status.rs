@@ -0,0 +1,236 @@
+use crate::{
+    cache::{self, Endpoint},
+    region::server::ServerRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlatformData {
+    pub id: String,
+    pub name: String,
+    pub locales: Vec<String>,
+    pub maintenances: Vec<Status>,
+    pub incidents: Vec<Status>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Status {
+    pub id: i64,
+    /// Only set for maintenances
+    pub maintenance_status: Option<MaintenanceStatus>,
+    /// Only set for incidents
+    pub incident_severity: Option<IncidentSeverity>,
+    pub titles: Vec<Content>,
+    pub updates: Vec<Update>,
+    pub created_at: String,
+    pub archive_at: Option<String>,
+    pub updated_at: Option<String>,
+    /// Affected platforms, e.g. "windows" or "macos"
+    pub platforms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceStatus {
+    Scheduled,
+    InProgress,
+    Complete,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Content {
+    pub locale: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Update {
+    pub id: i64,
+    pub author: String,
+    pub publish: bool,
+    /// Where the update is shown, e.g. "riotclient", "riotstatus" or "game"
+    pub publish_locations: Vec<String>,
+    pub translations: Vec<Content>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+}
+
+/// Change to an incident or maintenance between two platform data snapshots.
+#[derive(Debug)]
+pub enum StatusChange<'a> {
+    /// Present now but not in the previous snapshot
+    Opened(&'a Status),
+    /// Present in both snapshots but with a different update time or updates
+    Updated(&'a Status),
+    /// Present in the previous snapshot but no longer listed, holds the previous status
+    Resolved(&'a Status),
+}
+
+impl PlatformData {
+    pub async fn by_region(region: &ServerRegion, api_key: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/status/v4/platform-data?api_key={}",
+            region, api_key
+        );
+        let resp = cache::get(Endpoint::Status, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Incidents opened, updated or resolved since the `previous` snapshot.
+    pub fn incident_changes<'a>(&'a self, previous: &'a PlatformData) -> Vec<StatusChange<'a>> {
+        status_changes(&previous.incidents, &self.incidents)
+    }
+
+    /// Maintenances opened, updated or resolved since the `previous` snapshot.
+    pub fn maintenance_changes<'a>(&'a self, previous: &'a PlatformData) -> Vec<StatusChange<'a>> {
+        status_changes(&previous.maintenances, &self.maintenances)
+    }
+}
+
+impl Status {
+    /// Title in the given locale, e.g. "en_US".
+    pub fn title(&self, locale: &str) -> Option<&String> {
+        localized(&self.titles, locale)
+    }
+
+    /// Most recently created update, if any.
+    pub fn latest_update(&self) -> Option<&Update> {
+        self.updates
+            .iter()
+            .max_by(|a, b| a.created_at.cmp(&b.created_at))
+    }
+
+    fn last_changed(&self) -> (&str, usize) {
+        let updated_at = self.updated_at.as_deref().unwrap_or(&self.created_at);
+        (updated_at, self.updates.len())
+    }
+}
+
+impl Update {
+    /// Update text in the given locale, e.g. "en_US".
+    pub fn translation(&self, locale: &str) -> Option<&String> {
+        localized(&self.translations, locale)
+    }
+}
+
+fn localized<'a>(contents: &'a [Content], locale: &str) -> Option<&'a String> {
+    contents
+        .iter()
+        .find(|content| content.locale == locale)
+        .map(|content| &content.content)
+}
+
+fn status_changes<'a>(previous: &'a [Status], current: &'a [Status]) -> Vec<StatusChange<'a>> {
+    let mut changes = Vec::new();
+    for status in current {
+        match previous.iter().find(|old| old.id == status.id) {
+            None => changes.push(StatusChange::Opened(status)),
+            Some(old) if old.last_changed() != status.last_changed() => {
+                changes.push(StatusChange::Updated(status))
+            }
+            Some(_) => {}
+        }
+    }
+    for old in previous {
+        if !current.iter().any(|status| status.id == old.id) {
+            changes.push(StatusChange::Resolved(old));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: i64, updated_at: Option<&str>, updates: usize) -> Status {
+        Status {
+            id,
+            maintenance_status: None,
+            incident_severity: Some(IncidentSeverity::Warning),
+            titles: vec![Content {
+                locale: "en_US".to_string(),
+                content: format!("Incident {}", id),
+            }],
+            updates: (0..updates)
+                .map(|update| Update {
+                    id: update as i64,
+                    author: String::new(),
+                    publish: true,
+                    publish_locations: vec!["riotclient".to_string()],
+                    translations: vec![],
+                    created_at: format!("2024-01-01T00:0{}:00Z", update),
+                    updated_at: None,
+                })
+                .collect(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            archive_at: None,
+            updated_at: updated_at.map(str::to_string),
+            platforms: vec!["windows".to_string()],
+        }
+    }
+
+    fn ids(changes: &[StatusChange]) -> Vec<(&'static str, i64)> {
+        changes
+            .iter()
+            .map(|change| match change {
+                StatusChange::Opened(status) => ("opened", status.id),
+                StatusChange::Updated(status) => ("updated", status.id),
+                StatusChange::Resolved(status) => ("resolved", status.id),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn opened_updated_and_resolved() {
+        let previous = vec![
+            status(1, None, 1),
+            status(2, None, 1),
+            status(3, Some("2024-01-01T01:00:00Z"), 1),
+        ];
+        let current = vec![
+            status(1, None, 1),
+            status(2, None, 2),
+            status(3, Some("2024-01-01T02:00:00Z"), 1),
+            status(4, None, 0),
+        ];
+        assert_eq!(
+            ids(&status_changes(&previous, &current)),
+            vec![("updated", 2), ("updated", 3), ("opened", 4)]
+        );
+
+        let resolved = status_changes(&current, &previous[..1]);
+        assert_eq!(
+            ids(&resolved),
+            vec![("resolved", 2), ("resolved", 3), ("resolved", 4)]
+        );
+        // Resolved changes hold the status of the previous snapshot
+        let StatusChange::Resolved(status) = &resolved[0] else {
+            panic!("expected a resolved status");
+        };
+        assert_eq!(status.updates.len(), 2);
+    }
+
+    #[test]
+    fn unchanged_snapshot_has_no_changes() {
+        let snapshot = vec![status(1, Some("2024-01-01T01:00:00Z"), 1)];
+        assert!(status_changes(&snapshot, &snapshot.clone()).is_empty());
+        assert_eq!(snapshot[0].title("en_US").unwrap(), "Incident 1");
+        assert_eq!(snapshot[0].latest_update().unwrap().id, 0);
+    }
+}
@@ -0,0 +1,783 @@
+//! SQLite storage for accounts, summoners, league entry snapshots, matches and timelines.
+//!
+//! Matches are split into `matches`, `participants`, `teams` and `bans` rows, timelines are
+//! kept as the raw JSON returned by `Timeline::raw_by_match_id`. Timestamps are milliseconds
+//! since the Unix epoch, like the ones in `Info`, except `sync_checkpoints.synced_at`, which
+//! is in seconds like `SyncCheckpoint::synced_at` and the `startTime` of match-v5.
+
+pub mod schema;
+
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{params, types::Type, Connection, OptionalExtension, Row};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    account::RiotAccount,
+    league::LeagueEntry,
+    lol_match::Match,
+    player_sync::SyncCheckpoint,
+    queue::ranked::RankedQueue,
+    ranked::{division::RankedDivision, tier::RankedTier},
+    region::server::ServerRegion,
+    summoner::Summoner,
+};
+
+use self::schema::MIGRATIONS;
+
+pub use rusqlite::{Error, Result};
+
+/// Connection to a SQLite database migrated to the latest schema.
+#[derive(Debug)]
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SummonerRow {
+    pub puuid: String,
+    pub region: ServerRegion,
+    pub summoner_id: String,
+    pub account_id: String,
+    pub profile_icon_id: u32,
+    pub summoner_level: u64,
+    pub revision_date: u64,
+    pub updated_at: u64,
+}
+
+/// A `LeagueEntry` as it was at `recorded_at`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LeagueSnapshot {
+    pub region: ServerRegion,
+    pub puuid: Option<String>,
+    pub summoner_id: String,
+    pub league_id: String,
+    pub queue_type: RankedQueue,
+    pub tier: RankedTier,
+    pub rank: RankedDivision,
+    pub league_points: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub hot_streak: bool,
+    pub veteran: bool,
+    pub fresh_blood: bool,
+    pub inactive: bool,
+    pub recorded_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MatchRow {
+    pub match_id: String,
+    pub platform_id: String,
+    pub queue_id: i64,
+    pub game_mode: String,
+    pub game_version: String,
+    pub map_id: i64,
+    pub game_creation: u64,
+    pub game_duration: u64,
+    pub game_end_timestamp: Option<u64>,
+    pub end_of_game_result: String,
+    pub tournament_code: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParticipantRow {
+    pub match_id: String,
+    pub participant_id: i64,
+    pub puuid: String,
+    pub riot_id_game_name: String,
+    pub riot_id_tagline: String,
+    pub team_id: i64,
+    pub team_position: String,
+    pub champion_id: i64,
+    pub champion_name: String,
+    pub champ_level: i64,
+    pub win: bool,
+    pub kills: i64,
+    pub deaths: i64,
+    pub assists: i64,
+    pub gold_earned: i64,
+    pub total_damage_dealt_to_champions: i64,
+    pub total_minions_killed: i64,
+    pub neutral_minions_killed: i64,
+    pub vision_score: i64,
+    pub items: [i64; 7],
+    pub summoner1_id: i64,
+    pub summoner2_id: i64,
+    pub primary_style: Option<i64>,
+    pub sub_style: Option<i64>,
+    pub keystone: Option<i64>,
+}
+
+impl SqliteStore {
+    /// Opens or creates a database file and migrates it.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    /// Migrates an existing connection.
+    pub fn new(conn: Connection) -> Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// Number of migrations applied to the database.
+    pub fn schema_version(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        Ok(version as usize)
+    }
+
+    fn migrate(&self) -> Result<()> {
+        let applied = self.schema_version()?;
+        let mut conn = self.conn.lock().unwrap();
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+            let tx = conn.transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", version as i64 + 1)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    pub fn upsert_account(&self, account: &RiotAccount) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO accounts (puuid, game_name, tag_line, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (puuid) DO UPDATE SET
+                game_name = excluded.game_name,
+                tag_line = excluded.tag_line,
+                updated_at = excluded.updated_at",
+            params![
+                account.get_puuid(),
+                account.get_game_name(),
+                account.get_tag_line(),
+                now(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn upsert_summoner(&self, region: &ServerRegion, summoner: &Summoner) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO summoners (puuid, region, summoner_id, account_id, profile_icon_id,
+                summoner_level, revision_date, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (puuid) DO UPDATE SET
+                region = excluded.region,
+                summoner_id = excluded.summoner_id,
+                account_id = excluded.account_id,
+                profile_icon_id = excluded.profile_icon_id,
+                summoner_level = excluded.summoner_level,
+                revision_date = excluded.revision_date,
+                updated_at = excluded.updated_at",
+            params![
+                summoner.get_puuid(),
+                to_text(region),
+                summoner.get_id(),
+                summoner.get_account_id(),
+                summoner.get_profile_icon_id(),
+                summoner.get_summoner_level(),
+                summoner.get_revision_date(),
+                now(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Records the entries as they are now. Entries are snapshots, so previous ones are kept.
+    ///
+    /// `puuid` is used for entries predating `LeagueEntry::get_puuid`.
+    pub fn insert_league_entries(
+        &self,
+        region: &ServerRegion,
+        puuid: Option<&str>,
+        entries: &[LeagueEntry],
+    ) -> Result<()> {
+        let recorded_at = now();
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO league_entries (region, puuid, summoner_id, league_id, queue_type,
+                    tier, rank, league_points, wins, losses, hot_streak, veteran, fresh_blood,
+                    inactive, recorded_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            )?;
+            for entry in entries {
+                insert.execute(params![
+                    to_text(region),
+                    entry.get_puuid().as_deref().or(puuid),
+                    entry.get_summoner_id(),
+                    entry.get_league_id(),
+                    to_text(&entry.get_queue_type()),
+                    to_text(&entry.get_tier()),
+                    to_text(&entry.get_rank()),
+                    entry.get_league_points(),
+                    entry.get_wins(),
+                    entry.get_losses(),
+                    entry.get_hot_streak(),
+                    entry.get_veteran(),
+                    entry.get_fresh_blood(),
+                    entry.get_inactive(),
+                    recorded_at,
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Stores a match with its participants, teams and bans, replacing any previous copy.
+    pub fn upsert_match(&self, game: &Match) -> Result<()> {
+        let match_id = &game.metadata.match_id;
+        let info = &game.info;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO matches (match_id, platform_id, queue_id, game_mode, game_version, map_id,
+                game_creation, game_duration, game_end_timestamp, end_of_game_result,
+                tournament_code)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT (match_id) DO UPDATE SET
+                platform_id = excluded.platform_id,
+                queue_id = excluded.queue_id,
+                game_mode = excluded.game_mode,
+                game_version = excluded.game_version,
+                map_id = excluded.map_id,
+                game_creation = excluded.game_creation,
+                game_duration = excluded.game_duration,
+                game_end_timestamp = excluded.game_end_timestamp,
+                end_of_game_result = excluded.end_of_game_result,
+                tournament_code = excluded.tournament_code",
+            params![
+                match_id,
+                info.platform_id,
+                info.queue_id,
+                info.game_mode,
+                info.game_version,
+                info.map_id,
+                info.game_creation,
+                info.game_duration,
+                info.game_end_timestamp,
+                info.end_of_game_result,
+                info.tournament_code,
+            ],
+        )?;
+        for table in ["participants", "teams", "bans"] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE match_id = ?1", table),
+                [match_id],
+            )?;
+        }
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO participants (match_id, participant_id, puuid, riot_id_game_name,
+                    riot_id_tagline, team_id, team_position, champion_id, champion_name,
+                    champ_level, win, kills, deaths, assists, gold_earned,
+                    total_damage_dealt_to_champions, total_minions_killed, neutral_minions_killed,
+                    vision_score, item_0, item_1, item_2, item_3, item_4, item_5, item_6,
+                    summoner1_id, summoner2_id, primary_style, sub_style, keystone)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                    ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31)",
+            )?;
+            for participant in &info.participants {
+                let perks = participant.get_perks();
+                let primary = perks.primary_style();
+                let items = participant.get_items();
+                insert.execute(params![
+                    match_id,
+                    participant.get_participant_id(),
+                    participant.get_puuid(),
+                    participant.get_riot_id_game_name(),
+                    participant.get_riot_id_tagline(),
+                    participant.get_team_id(),
+                    participant.get_team_position(),
+                    participant.get_champion_id(),
+                    participant.get_champion_name(),
+                    participant.get_champ_level(),
+                    participant.get_win(),
+                    participant.get_kills(),
+                    participant.get_deaths(),
+                    participant.get_assists(),
+                    participant.get_gold_earned(),
+                    participant.get_total_damage_dealt_to_champions(),
+                    participant.get_total_minions_killed(),
+                    participant.get_neutral_minions_killed(),
+                    participant.get_vision_score(),
+                    items[0],
+                    items[1],
+                    items[2],
+                    items[3],
+                    items[4],
+                    items[5],
+                    items[6],
+                    participant.get_summoner1_id(),
+                    participant.get_summoner2_id(),
+                    primary.map(|style| style.get_style()),
+                    perks.sub_style().map(|style| style.get_style()),
+                    primary
+                        .and_then(|style| style.get_selections().first())
+                        .map(|selection| selection.get_perk()),
+                ])?;
+            }
+            let mut insert_team =
+                tx.prepare("INSERT INTO teams (match_id, team_id, win) VALUES (?1, ?2, ?3)")?;
+            let mut insert_ban = tx.prepare(
+                "INSERT INTO bans (match_id, team_id, pick_turn, champion_id)
+                VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for team in &info.teams {
+                insert_team.execute(params![match_id, team.get_team_id(), team.get_win()])?;
+                for ban in team.get_bans() {
+                    insert_ban.execute(params![
+                        match_id,
+                        team.get_team_id(),
+                        ban.get_pick_turn(),
+                        ban.get_champion_id(),
+                    ])?;
+                }
+            }
+        }
+        tx.commit()
+    }
+
+    /// Stores a timeline body from `Timeline::raw_by_match_id`, replacing any previous one.
+    pub fn upsert_timeline(&self, match_id: &str, body: &str) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO timelines (match_id, body) VALUES (?1, ?2)
+            ON CONFLICT (match_id) DO UPDATE SET body = excluded.body",
+            params![match_id, body],
+        )?;
+        Ok(())
+    }
+
+    pub fn account(&self, puuid: &str) -> Result<Option<RiotAccount>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT puuid, game_name, tag_line FROM accounts WHERE puuid = ?1",
+                [puuid],
+                |row| Ok(RiotAccount::new(row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+    }
+
+    /// Finds an account by Riot ID, case insensitive.
+    pub fn account_by_riot_id(
+        &self,
+        game_name: &str,
+        tag_line: &str,
+    ) -> Result<Option<RiotAccount>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT puuid, game_name, tag_line FROM accounts
+                WHERE game_name = ?1 COLLATE NOCASE AND tag_line = ?2 COLLATE NOCASE",
+                [game_name, tag_line],
+                |row| Ok(RiotAccount::new(row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+    }
+
+    pub fn summoner(&self, puuid: &str) -> Result<Option<SummonerRow>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT puuid, region, summoner_id, account_id, profile_icon_id, summoner_level,
+                    revision_date, updated_at
+                FROM summoners WHERE puuid = ?1",
+                [puuid],
+                |row| {
+                    Ok(SummonerRow {
+                        puuid: row.get(0)?,
+                        region: from_text(row, 1)?,
+                        summoner_id: row.get(2)?,
+                        account_id: row.get(3)?,
+                        profile_icon_id: row.get(4)?,
+                        summoner_level: row.get(5)?,
+                        revision_date: row.get(6)?,
+                        updated_at: row.get(7)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Every snapshot of a player's entry in a queue, oldest first.
+    pub fn league_history(&self, puuid: &str, queue: RankedQueue) -> Result<Vec<LeagueSnapshot>> {
+        let conn = self.conn.lock().unwrap();
+        let mut select = conn.prepare(
+            "SELECT region, puuid, summoner_id, league_id, queue_type, tier, rank, league_points,
+                wins, losses, hot_streak, veteran, fresh_blood, inactive, recorded_at
+            FROM league_entries WHERE puuid = ?1 AND queue_type = ?2
+            ORDER BY recorded_at",
+        )?;
+        let snapshots = select.query_map(params![puuid, to_text(&queue)], |row| {
+            Ok(LeagueSnapshot {
+                region: from_text(row, 0)?,
+                puuid: row.get(1)?,
+                summoner_id: row.get(2)?,
+                league_id: row.get(3)?,
+                queue_type: from_text(row, 4)?,
+                tier: from_text(row, 5)?,
+                rank: from_text(row, 6)?,
+                league_points: row.get(7)?,
+                wins: row.get(8)?,
+                losses: row.get(9)?,
+                hot_streak: row.get(10)?,
+                veteran: row.get(11)?,
+                fresh_blood: row.get(12)?,
+                inactive: row.get(13)?,
+                recorded_at: row.get(14)?,
+            })
+        })?;
+        snapshots.collect()
+    }
+
+    pub fn has_match(&self, match_id: &str) -> Result<bool> {
+        self.exists("SELECT 1 FROM matches WHERE match_id = ?1", match_id)
+    }
+
+    pub fn has_timeline(&self, match_id: &str) -> Result<bool> {
+        self.exists("SELECT 1 FROM timelines WHERE match_id = ?1", match_id)
+    }
+
+    pub fn match_row(&self, match_id: &str) -> Result<Option<MatchRow>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT match_id, platform_id, queue_id, game_mode, game_version, map_id,
+                    game_creation, game_duration, game_end_timestamp, end_of_game_result,
+                    tournament_code
+                FROM matches WHERE match_id = ?1",
+                [match_id],
+                |row| {
+                    Ok(MatchRow {
+                        match_id: row.get(0)?,
+                        platform_id: row.get(1)?,
+                        queue_id: row.get(2)?,
+                        game_mode: row.get(3)?,
+                        game_version: row.get(4)?,
+                        map_id: row.get(5)?,
+                        game_creation: row.get(6)?,
+                        game_duration: row.get(7)?,
+                        game_end_timestamp: row.get(8)?,
+                        end_of_game_result: row.get(9)?,
+                        tournament_code: row.get(10)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    pub fn participants(&self, match_id: &str) -> Result<Vec<ParticipantRow>> {
+        let conn = self.conn.lock().unwrap();
+        let mut select = conn.prepare(
+            "SELECT match_id, participant_id, puuid, riot_id_game_name, riot_id_tagline, team_id,
+                team_position, champion_id, champion_name, champ_level, win, kills, deaths,
+                assists, gold_earned, total_damage_dealt_to_champions, total_minions_killed,
+                neutral_minions_killed, vision_score, item_0, item_1, item_2, item_3, item_4,
+                item_5, item_6, summoner1_id, summoner2_id, primary_style, sub_style, keystone
+            FROM participants WHERE match_id = ?1 ORDER BY participant_id",
+        )?;
+        let participants = select.query_map([match_id], |row| {
+            Ok(ParticipantRow {
+                match_id: row.get(0)?,
+                participant_id: row.get(1)?,
+                puuid: row.get(2)?,
+                riot_id_game_name: row.get(3)?,
+                riot_id_tagline: row.get(4)?,
+                team_id: row.get(5)?,
+                team_position: row.get(6)?,
+                champion_id: row.get(7)?,
+                champion_name: row.get(8)?,
+                champ_level: row.get(9)?,
+                win: row.get(10)?,
+                kills: row.get(11)?,
+                deaths: row.get(12)?,
+                assists: row.get(13)?,
+                gold_earned: row.get(14)?,
+                total_damage_dealt_to_champions: row.get(15)?,
+                total_minions_killed: row.get(16)?,
+                neutral_minions_killed: row.get(17)?,
+                vision_score: row.get(18)?,
+                items: [
+                    row.get(19)?,
+                    row.get(20)?,
+                    row.get(21)?,
+                    row.get(22)?,
+                    row.get(23)?,
+                    row.get(24)?,
+                    row.get(25)?,
+                ],
+                summoner1_id: row.get(26)?,
+                summoner2_id: row.get(27)?,
+                primary_style: row.get(28)?,
+                sub_style: row.get(29)?,
+                keystone: row.get(30)?,
+            })
+        })?;
+        participants.collect()
+    }
+
+    /// IDs of the stored matches a player took part in, newest first.
+    ///
+    /// `queue_id` filters by `Info.queue_id`, e.g. 420 for ranked solo, and `since` by
+    /// `Info.game_creation`.
+    pub fn match_ids_by_puuid(
+        &self,
+        puuid: &str,
+        queue_id: Option<i64>,
+        since: Option<u64>,
+    ) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut select = conn.prepare(
+            "SELECT DISTINCT matches.match_id FROM matches
+            JOIN participants ON participants.match_id = matches.match_id
+            WHERE participants.puuid = ?1
+                AND (?2 IS NULL OR matches.queue_id = ?2)
+                AND (?3 IS NULL OR matches.game_creation >= ?3)
+            ORDER BY matches.game_creation DESC",
+        )?;
+        let ids = select.query_map(params![puuid, queue_id, since], |row| row.get(0))?;
+        ids.collect()
+    }
+
+    pub fn timeline(&self, match_id: &str) -> Result<Option<String>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT body FROM timelines WHERE match_id = ?1",
+                [match_id],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    pub fn sync_checkpoint(&self, puuid: &str) -> Result<Option<SyncCheckpoint>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT puuid, synced_at FROM sync_checkpoints WHERE puuid = ?1",
+                [puuid],
+                |row| {
+                    Ok(SyncCheckpoint {
+                        puuid: row.get(0)?,
+                        synced_at: row.get(1)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    pub fn upsert_sync_checkpoint(&self, checkpoint: &SyncCheckpoint) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO sync_checkpoints (puuid, synced_at) VALUES (?1, ?2)
+            ON CONFLICT (puuid) DO UPDATE SET synced_at = excluded.synced_at",
+            params![checkpoint.puuid, checkpoint.synced_at],
+        )?;
+        Ok(())
+    }
+
+    fn exists(&self, query: &str, key: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let found = conn.query_row(query, [key], |_| Ok(())).optional()?;
+        Ok(found.is_some())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Stores enums by their API name, e.g. "RANKED_SOLO_5x5".
+fn to_text<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(value) => value.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn from_text<T: DeserializeOwned>(row: &Row, index: usize) -> Result<T> {
+    let text: String = row.get(index)?;
+    serde_json::from_value(serde_json::Value::String(text))
+        .map_err(|e| Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn count(store: &SqliteStore, table: &str, match_id: &str) -> i64 {
+        let conn = store.conn.lock().unwrap();
+        let query = format!("SELECT COUNT(*) FROM {} WHERE match_id = ?1", table);
+        conn.query_row(&query, [match_id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn migrations_are_applied_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("league.sqlite");
+        let store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+        store
+            .upsert_match(&fixtures::ranked_game("EUW1_1", 1000))
+            .unwrap();
+        drop(store);
+
+        // Reopening runs no migration again, which would fail on the existing tables
+        let store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len());
+        assert!(store.has_match("EUW1_1").unwrap());
+    }
+
+    #[test]
+    fn upsert_match_replaces_previous_rows() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let mut game = fixtures::game(
+            "EUW1_1",
+            420,
+            1000,
+            vec![
+                fixtures::participant("a", 100, "TOP", 1, true),
+                fixtures::participant("b", 200, "TOP", 2, false),
+                fixtures::participant("c", 200, "JUNGLE", 3, false),
+            ],
+        );
+        fixtures::ban(&mut game, 100, 10);
+        fixtures::ban(&mut game, 200, 11);
+        store.upsert_match(&fixtures::parse(game)).unwrap();
+        assert_eq!(store.participants("EUW1_1").unwrap().len(), 3);
+        assert_eq!(count(&store, "bans", "EUW1_1"), 2);
+
+        let game = fixtures::game(
+            "EUW1_1",
+            440,
+            2000,
+            vec![
+                fixtures::participant("a", 100, "TOP", 4, false),
+                fixtures::participant("b", 200, "TOP", 5, true),
+            ],
+        );
+        store.upsert_match(&fixtures::parse(game)).unwrap();
+        let row = store.match_row("EUW1_1").unwrap().unwrap();
+        assert_eq!((row.queue_id, row.game_creation), (440, 2000));
+        let participants = store.participants("EUW1_1").unwrap();
+        let champions: Vec<(&str, i64, bool)> = participants
+            .iter()
+            .map(|row| (row.puuid.as_str(), row.champion_id, row.win))
+            .collect();
+        assert_eq!(champions, vec![("a", 4, false), ("b", 5, true)]);
+        assert_eq!(count(&store, "bans", "EUW1_1"), 0);
+        assert_eq!(count(&store, "teams", "EUW1_1"), 2);
+
+        store.upsert_timeline("EUW1_1", "{}").unwrap();
+        store.upsert_timeline("EUW1_1", "{\"info\":{}}").unwrap();
+        assert_eq!(
+            store.timeline("EUW1_1").unwrap().as_deref(),
+            Some("{\"info\":{}}")
+        );
+    }
+
+    #[test]
+    fn styles_are_stored_by_description() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let mut player = fixtures::participant("a", 100, "MIDDLE", 1, true);
+        // match-v5 may list the secondary style first
+        player["perks"]["styles"] = serde_json::json!([
+            {
+                "description": "subStyle",
+                "selections": [
+                    {"perk": 8473, "var1": 0, "var2": 0, "var3": 0},
+                    {"perk": 8451, "var1": 0, "var2": 0, "var3": 0}
+                ],
+                "style": 8400
+            },
+            {
+                "description": "primaryStyle",
+                "selections": [
+                    {"perk": 8010, "var1": 0, "var2": 0, "var3": 0},
+                    {"perk": 9111, "var1": 0, "var2": 0, "var3": 0},
+                    {"perk": 9104, "var1": 0, "var2": 0, "var3": 0},
+                    {"perk": 8299, "var1": 0, "var2": 0, "var3": 0}
+                ],
+                "style": 8000
+            }
+        ]);
+        let game = fixtures::game("EUW1_1", 420, 1000, vec![player]);
+        store.upsert_match(&fixtures::parse(game)).unwrap();
+
+        let participants = store.participants("EUW1_1").unwrap();
+        let styles = (
+            participants[0].primary_style,
+            participants[0].sub_style,
+            participants[0].keystone,
+        );
+        assert_eq!(styles, (Some(8000), Some(8400), Some(8010)));
+    }
+
+    #[test]
+    fn match_ids_by_puuid_filters_by_queue_and_creation() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let player = |puuid: &str| fixtures::participant(puuid, 100, "TOP", 1, true);
+        for (match_id, queue_id, game_creation, puuid) in [
+            ("EUW1_1", 420, 1000, "a"),
+            ("EUW1_2", 440, 2000, "a"),
+            ("EUW1_3", 420, 3000, "a"),
+            ("EUW1_4", 420, 4000, "b"),
+        ] {
+            let game = fixtures::game(match_id, queue_id, game_creation, vec![player(puuid)]);
+            store.upsert_match(&fixtures::parse(game)).unwrap();
+        }
+
+        let ids = |queue_id, since| store.match_ids_by_puuid("a", queue_id, since).unwrap();
+        assert_eq!(ids(None, None), vec!["EUW1_3", "EUW1_2", "EUW1_1"]);
+        assert_eq!(ids(Some(420), None), vec!["EUW1_3", "EUW1_1"]);
+        assert_eq!(ids(None, Some(2000)), vec!["EUW1_3", "EUW1_2"]);
+        assert_eq!(ids(Some(440), Some(2001)), Vec::<String>::new());
+        assert!(store
+            .match_ids_by_puuid("c", None, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn sync_checkpoints_are_replaced() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        assert!(store.sync_checkpoint("a").unwrap().is_none());
+        for synced_at in [1_700_000_000, 1_700_003_600] {
+            store
+                .upsert_sync_checkpoint(&SyncCheckpoint {
+                    puuid: "a".to_string(),
+                    synced_at,
+                })
+                .unwrap();
+        }
+        let checkpoint = store.sync_checkpoint("a").unwrap().unwrap();
+        assert_eq!(checkpoint.synced_at, 1_700_003_600);
+    }
+}
@@ -0,0 +1,126 @@
+/// Schema migrations, applied in order. The number of applied migrations is kept in
+/// `PRAGMA user_version`, so migrations must never be edited once released, only appended.
+pub const MIGRATIONS: &[&str] = &[
+    // 1: accounts, summoners, league entry snapshots, matches and timelines
+    "
+    CREATE TABLE accounts (
+        puuid TEXT PRIMARY KEY,
+        game_name TEXT,
+        tag_line TEXT,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE INDEX accounts_riot_id ON accounts (game_name COLLATE NOCASE, tag_line COLLATE NOCASE);
+
+    CREATE TABLE summoners (
+        puuid TEXT PRIMARY KEY,
+        region TEXT NOT NULL,
+        summoner_id TEXT NOT NULL,
+        account_id TEXT NOT NULL,
+        profile_icon_id INTEGER NOT NULL,
+        summoner_level INTEGER NOT NULL,
+        revision_date INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE INDEX summoners_summoner_id ON summoners (summoner_id);
+
+    CREATE TABLE league_entries (
+        id INTEGER PRIMARY KEY,
+        region TEXT NOT NULL,
+        puuid TEXT,
+        summoner_id TEXT NOT NULL,
+        league_id TEXT NOT NULL,
+        queue_type TEXT NOT NULL,
+        tier TEXT NOT NULL,
+        rank TEXT NOT NULL,
+        league_points INTEGER NOT NULL,
+        wins INTEGER NOT NULL,
+        losses INTEGER NOT NULL,
+        hot_streak INTEGER NOT NULL,
+        veteran INTEGER NOT NULL,
+        fresh_blood INTEGER NOT NULL,
+        inactive INTEGER NOT NULL,
+        recorded_at INTEGER NOT NULL
+    );
+    CREATE INDEX league_entries_puuid ON league_entries (puuid, queue_type, recorded_at);
+    CREATE INDEX league_entries_summoner_id ON league_entries (summoner_id, queue_type, recorded_at);
+
+    CREATE TABLE matches (
+        match_id TEXT PRIMARY KEY,
+        platform_id TEXT NOT NULL,
+        queue_id INTEGER NOT NULL,
+        game_mode TEXT NOT NULL,
+        game_version TEXT NOT NULL,
+        map_id INTEGER NOT NULL,
+        game_creation INTEGER NOT NULL,
+        game_duration INTEGER NOT NULL,
+        game_end_timestamp INTEGER,
+        end_of_game_result TEXT NOT NULL,
+        tournament_code TEXT
+    );
+    CREATE INDEX matches_queue_id ON matches (queue_id, game_creation);
+
+    CREATE TABLE participants (
+        match_id TEXT NOT NULL REFERENCES matches (match_id) ON DELETE CASCADE,
+        participant_id INTEGER NOT NULL,
+        puuid TEXT NOT NULL,
+        riot_id_game_name TEXT NOT NULL,
+        riot_id_tagline TEXT NOT NULL,
+        team_id INTEGER NOT NULL,
+        team_position TEXT NOT NULL,
+        champion_id INTEGER NOT NULL,
+        champion_name TEXT NOT NULL,
+        champ_level INTEGER NOT NULL,
+        win INTEGER NOT NULL,
+        kills INTEGER NOT NULL,
+        deaths INTEGER NOT NULL,
+        assists INTEGER NOT NULL,
+        gold_earned INTEGER NOT NULL,
+        total_damage_dealt_to_champions INTEGER NOT NULL,
+        total_minions_killed INTEGER NOT NULL,
+        neutral_minions_killed INTEGER NOT NULL,
+        vision_score INTEGER NOT NULL,
+        item_0 INTEGER NOT NULL,
+        item_1 INTEGER NOT NULL,
+        item_2 INTEGER NOT NULL,
+        item_3 INTEGER NOT NULL,
+        item_4 INTEGER NOT NULL,
+        item_5 INTEGER NOT NULL,
+        item_6 INTEGER NOT NULL,
+        summoner1_id INTEGER NOT NULL,
+        summoner2_id INTEGER NOT NULL,
+        primary_style INTEGER,
+        sub_style INTEGER,
+        keystone INTEGER,
+        PRIMARY KEY (match_id, participant_id)
+    );
+    CREATE INDEX participants_puuid ON participants (puuid);
+    CREATE INDEX participants_champion_id ON participants (champion_id);
+
+    CREATE TABLE teams (
+        match_id TEXT NOT NULL REFERENCES matches (match_id) ON DELETE CASCADE,
+        team_id INTEGER NOT NULL,
+        win INTEGER NOT NULL,
+        PRIMARY KEY (match_id, team_id)
+    );
+
+    CREATE TABLE bans (
+        match_id TEXT NOT NULL REFERENCES matches (match_id) ON DELETE CASCADE,
+        team_id INTEGER NOT NULL,
+        pick_turn INTEGER NOT NULL,
+        champion_id INTEGER NOT NULL,
+        PRIMARY KEY (match_id, team_id, pick_turn)
+    );
+
+    CREATE TABLE timelines (
+        match_id TEXT PRIMARY KEY,
+        body TEXT NOT NULL
+    );
+    ",
+    // 2: checkpoints of `PlayerSync`, in epoch seconds like the `startTime` of match-v5
+    "
+    CREATE TABLE sync_checkpoints (
+        puuid TEXT PRIMARY KEY,
+        synced_at INTEGER NOT NULL
+    );
+    ",
+];
@@ -1,5 +1,8 @@
-use crate::region::server::ServerRegion;
-use log::{debug, error};
+use crate::{
+    cache::{self, Endpoint},
+    region::server::ServerRegion,
+};
+use log::error;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -17,23 +20,25 @@ pub struct Summoner {
 }
 
 impl Summoner {
-    pub async fn by_rso_puuid(
-        region: &ServerRegion,
-        api_key: &String,
-        rso_puuid: &String,
-    ) -> Option<Self> {
+    /// Requests the summoner of the player who authorised the given RSO access token.
+    ///
+    /// Unlike other endpoints this authenticates with the player's token instead of an API key.
+    pub async fn by_access_token(region: &ServerRegion, access_token: &String) -> Option<Self> {
         let request_url = format!(
-            "https://{}.api.riotgames.com/fulfillment/v1/summoners/by-puuid/{}?api_key={}",
-            region.to_string(),
-            rso_puuid,
-            api_key
+            "https://{}.api.riotgames.com/lol/summoner/v4/summoners/me",
+            region
         );
-        let resp = reqwest::get(request_url).await.unwrap();
+        let resp = reqwest::Client::new()
+            .get(request_url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .unwrap();
         let resp = resp.text().await.expect("Could not parse");
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
-                error!("{e:?}");
+                error!("{e:?}, {}", resp);
                 None
             }
         }
@@ -46,12 +51,9 @@ impl Summoner {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/summoner/v4/summoners/by-account/{}?api_key={}",
-            region.to_string(),
-            encrypted_account_id,
-            api_key
+            region, encrypted_account_id, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::Summoner, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -64,12 +66,9 @@ impl Summoner {
     pub async fn by_puuid(region: &ServerRegion, api_key: &String, puuid: &String) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/summoner/v4/summoners/by-puuid/{}?api_key={}",
-            region.to_string(),
-            puuid,
-            api_key
+            region, puuid, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::Summoner, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
@@ -86,12 +85,9 @@ impl Summoner {
     ) -> Option<Self> {
         let request_url = format!(
             "https://{}.api.riotgames.com/lol/summoner/v4/summoners/{}?api_key={}",
-            region.to_string(),
-            summoner_id,
-            api_key
+            region, summoner_id, api_key
         );
-        let resp = reqwest::get(request_url).await.unwrap();
-        let resp = resp.text().await.expect("Could not parse");
+        let resp = cache::get(Endpoint::Summoner, request_url).await;
         match serde_json::from_str(&resp) {
             Ok(t) => Some(t),
             Err(e) => {
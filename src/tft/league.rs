@@ -0,0 +1,218 @@
+use crate::{
+    cache::{self, Endpoint},
+    league::{LeagueItem, MiniSeries},
+    queue::tft::TftQueue,
+    ranked::{division::RankedDivision, tier::RankedTier},
+    region::server::ServerRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+/// Hyper roll ladder tiers, from lowest to highest.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RatedTier {
+    GRAY,
+    GREEN,
+    BLUE,
+    PURPLE,
+    ORANGE,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TftLeagueEntry {
+    pub puuid: Option<String>,
+    /// Not present for hyper roll
+    pub league_id: Option<String>,
+    pub summoner_id: Option<String>,
+    pub queue_type: TftQueue,
+    /// Only present for hyper roll
+    pub rated_tier: Option<RatedTier>,
+    /// Only present for hyper roll
+    pub rated_rating: Option<i64>,
+    /// Not present for hyper roll
+    pub tier: Option<RankedTier>,
+    /// Not present for hyper roll
+    pub rank: Option<RankedDivision>,
+    #[serde(default)]
+    pub league_points: u32,
+    pub wins: u32,
+    pub losses: u32,
+    #[serde(default)]
+    pub hot_streak: bool,
+    #[serde(default)]
+    pub veteran: bool,
+    #[serde(default)]
+    pub fresh_blood: bool,
+    #[serde(default)]
+    pub inactive: bool,
+    pub mini_series: Option<MiniSeries>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TftLeagueList {
+    pub league_id: String,
+    pub entries: Vec<LeagueItem>,
+    pub tier: RankedTier,
+    pub name: String,
+    pub queue: TftQueue,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TopRatedLadderEntry {
+    pub summoner_id: Option<String>,
+    pub puuid: Option<String>,
+    pub rated_tier: RatedTier,
+    pub rated_rating: i64,
+    pub wins: u32,
+    pub previous_update_ladder_position: i64,
+}
+
+impl TftLeagueEntry {
+    pub async fn by_puuid(
+        region: &ServerRegion,
+        api_key: &String,
+        puuid: &String,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/by-puuid/{}?api_key={}",
+            region, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests a page of entries for a tier and division, pages start at 1.
+    pub async fn entries(
+        region: &ServerRegion,
+        api_key: &String,
+        division: RankedDivision,
+        tier: RankedTier,
+        queue: TftQueue,
+        page: Option<u32>,
+    ) -> Option<Vec<Self>> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/entries/{}/{}?queue={}&api_key={}",
+            region, tier, division, queue, api_key
+        );
+        if let Some(page) = page {
+            request_url = format!("{}&page={}", request_url, page);
+        }
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl TftLeagueList {
+    pub async fn challenger_by_queue(
+        region: &ServerRegion,
+        api_key: &String,
+        queue: TftQueue,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/challenger?queue={}&api_key={}",
+            region, queue, api_key
+        );
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn grandmaster_by_queue(
+        region: &ServerRegion,
+        api_key: &String,
+        queue: TftQueue,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/grandmaster?queue={}&api_key={}",
+            region, queue, api_key
+        );
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn master_by_queue(
+        region: &ServerRegion,
+        api_key: &String,
+        queue: TftQueue,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/master?queue={}&api_key={}",
+            region, queue, api_key
+        );
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn leagues(
+        region: &ServerRegion,
+        api_key: &String,
+        league_id: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/leagues/{}?api_key={}",
+            region, league_id, api_key
+        );
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl TopRatedLadderEntry {
+    /// Requests the top of a rated ladder, only hyper roll (`RankedTftTurbo`) is rated.
+    pub async fn top(
+        region: &ServerRegion,
+        api_key: &String,
+        queue: TftQueue,
+    ) -> Option<Vec<Self>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/league/v1/rated-ladders/{}/top?api_key={}",
+            region, queue, api_key
+        );
+        let resp = cache::get(Endpoint::TftLeague, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
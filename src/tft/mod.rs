@@ -0,0 +1,4 @@
+pub mod league;
+pub mod status;
+pub mod summoner;
+pub mod tft_match;
@@ -0,0 +1,22 @@
+use crate::{
+    cache::{self, Endpoint},
+    region::server::ServerRegion,
+    status::PlatformData,
+};
+use log::error;
+
+/// Requests the TFT status of a platform, which shares its format with lol-status-v4.
+pub async fn platform_data(region: &ServerRegion, api_key: &String) -> Option<PlatformData> {
+    let request_url = format!(
+        "https://{}.api.riotgames.com/tft/status/v1/platform-data?api_key={}",
+        region, api_key
+    );
+    let resp = cache::get(Endpoint::Status, request_url).await;
+    match serde_json::from_str(&resp) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", resp);
+            None
+        }
+    }
+}
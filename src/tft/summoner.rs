@@ -0,0 +1,84 @@
+//! tft-summoner-v1, which returns the same summoner as lol-summoner-v4 but is accessible
+//! with a TFT API key.
+
+use crate::{
+    cache::{self, Endpoint},
+    region::server::ServerRegion,
+    summoner::Summoner,
+};
+use log::error;
+
+pub async fn by_puuid(region: &ServerRegion, api_key: &String, puuid: &String) -> Option<Summoner> {
+    let request_url = format!(
+        "https://{}.api.riotgames.com/tft/summoner/v1/summoners/by-puuid/{}?api_key={}",
+        region, puuid, api_key
+    );
+    let resp = cache::get(Endpoint::Summoner, request_url).await;
+    match serde_json::from_str(&resp) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", resp);
+            None
+        }
+    }
+}
+
+pub async fn by_account(
+    region: &ServerRegion,
+    api_key: &String,
+    encrypted_account_id: &String,
+) -> Option<Summoner> {
+    let request_url = format!(
+        "https://{}.api.riotgames.com/tft/summoner/v1/summoners/by-account/{}?api_key={}",
+        region, encrypted_account_id, api_key
+    );
+    let resp = cache::get(Endpoint::Summoner, request_url).await;
+    match serde_json::from_str(&resp) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", resp);
+            None
+        }
+    }
+}
+
+pub async fn by_summoner_id(
+    region: &ServerRegion,
+    api_key: &String,
+    summoner_id: &String,
+) -> Option<Summoner> {
+    let request_url = format!(
+        "https://{}.api.riotgames.com/tft/summoner/v1/summoners/{}?api_key={}",
+        region, summoner_id, api_key
+    );
+    let resp = cache::get(Endpoint::Summoner, request_url).await;
+    match serde_json::from_str(&resp) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", resp);
+            None
+        }
+    }
+}
+
+/// Requests the summoner of the player who authorised the given RSO access token.
+pub async fn by_access_token(region: &ServerRegion, access_token: &String) -> Option<Summoner> {
+    let request_url = format!(
+        "https://{}.api.riotgames.com/tft/summoner/v1/summoners/me",
+        region
+    );
+    let resp = reqwest::Client::new()
+        .get(request_url)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap();
+    let resp = resp.text().await.expect("Could not parse");
+    match serde_json::from_str(&resp) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", resp);
+            None
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    cache::{self, Endpoint},
+    region::routing::RoutingRegion,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TftMatch {
+    pub metadata: TftMetadata,
+    pub info: TftInfo,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TftMetadata {
+    pub data_version: String,
+    pub match_id: String,
+    /// List of participant PUUIDs
+    pub participants: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TftInfo {
+    #[serde(rename = "endOfGameResult")]
+    pub end_of_game_result: Option<String>,
+    #[serde(rename = "gameCreation")]
+    pub game_creation: Option<u64>,
+    #[serde(rename = "gameId")]
+    pub game_id: Option<u64>,
+    /// Unix timestamp in milliseconds
+    pub game_datetime: u64,
+    /// Game length in seconds
+    pub game_length: f64,
+    pub game_version: String,
+    #[serde(rename = "mapId")]
+    pub map_id: Option<i64>,
+    pub participants: Vec<TftParticipant>,
+    pub queue_id: i64,
+    /// e.g. "standard", "turbo" or "pairs"
+    pub tft_game_type: Option<String>,
+    pub tft_set_core_name: Option<String>,
+    pub tft_set_number: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TftParticipant {
+    /// Augment names, e.g. "TFT9_Augment_CyberneticBulk1"
+    #[serde(default)]
+    pub augments: Vec<String>,
+    pub companion: Companion,
+    pub gold_left: i64,
+    pub last_round: i64,
+    pub level: i64,
+    /// 1 to 8, or 1 to 4 in double up
+    pub placement: i64,
+    /// Only present in double up
+    pub partner_group_id: Option<i64>,
+    pub players_eliminated: i64,
+    pub puuid: String,
+    #[serde(rename = "riotIdGameName")]
+    pub riot_id_game_name: Option<String>,
+    #[serde(rename = "riotIdTagline")]
+    pub riot_id_tagline: Option<String>,
+    /// Seconds into the game the participant was eliminated
+    pub time_eliminated: f64,
+    pub total_damage_to_players: i64,
+    pub traits: Vec<Trait>,
+    pub units: Vec<Unit>,
+    pub win: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Companion {
+    #[serde(rename = "content_ID")]
+    pub content_id: String,
+    #[serde(rename = "item_ID")]
+    pub item_id: i64,
+    #[serde(rename = "skin_ID")]
+    pub skin_id: i64,
+    pub species: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Trait {
+    pub name: String,
+    pub num_units: i64,
+    /// 0 = inactive, 1 = bronze, 2 = silver, 3 = gold, 4 = chromatic
+    pub style: i64,
+    pub tier_current: i64,
+    pub tier_total: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Unit {
+    #[serde(rename = "itemNames", default)]
+    pub item_names: Vec<String>,
+    pub character_id: String,
+    /// Trait the unit was chosen for, only used in set 5
+    pub chosen: Option<String>,
+    pub name: String,
+    pub rarity: i64,
+    /// Star level
+    pub tier: i64,
+}
+
+impl TftMatch {
+    pub async fn by_match_id(
+        region: &RoutingRegion,
+        api_key: &String,
+        match_id: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/tft/match/v1/matches/{}?api_key={}",
+            region, match_id, api_key
+        );
+        let resp = cache::get(Endpoint::TftMatch, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {:?}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn by_puuid(
+        region: &RoutingRegion,
+        api_key: &String,
+        puuid: &String,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+        start: Option<i64>,
+        count: Option<i64>,
+    ) -> Option<Vec<String>> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/tft/match/v1/matches/by-puuid/{}/ids",
+            region, puuid,
+        );
+        if let Some(start) = start {
+            request_url = format!("{}?start={}", request_url, start);
+        } else {
+            request_url = format!("{}?start=0", request_url);
+        }
+        if let Some(start_time) = start_time {
+            request_url = format!("{}&startTime={}", request_url, start_time);
+        }
+        if let Some(end_time) = end_time {
+            request_url = format!("{}&endTime={}", request_url, end_time);
+        }
+        if let Some(count) = count {
+            request_url = format!("{}&count={}", request_url, count);
+        }
+        request_url = format!("{}&api_key={}", request_url, api_key);
+        let resp = cache::get(Endpoint::TftMatchIds, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {:?}", resp);
+                None
+            }
+        }
+    }
+
+    /// The participant with the given PUUID, if they played in this match.
+    pub fn participant(&self, puuid: &str) -> Option<&TftParticipant> {
+        self.info
+            .participants
+            .iter()
+            .find(|participant| participant.puuid == puuid)
+    }
+}
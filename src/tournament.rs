@@ -0,0 +1,402 @@
+use core::fmt;
+
+use crate::{
+    cache::{self, Endpoint},
+    lol_match::Match,
+    region::{routing::RoutingRegion, server::ServerRegion},
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+/// Selects between the live tournament API and the stub used during development.
+///
+/// The stub accepts the same requests but never creates real lobbies, and does not support
+/// updating codes or looking up games.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentApi {
+    Live,
+    Stub,
+}
+
+impl fmt::Display for TournamentApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Live => write!(f, "tournament"),
+            Self::Stub => write!(f, "tournament-stub"),
+        }
+    }
+}
+
+/// Region names used by provider registration, which differ from platform IDs.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentRegion {
+    BR,
+    EUNE,
+    EUW,
+    JP,
+    KR,
+    LAN,
+    LAS,
+    ME,
+    NA,
+    OCE,
+    PBE,
+    PH,
+    RU,
+    SG,
+    TH,
+    TR,
+    TW,
+    VN,
+}
+
+impl From<&ServerRegion> for TournamentRegion {
+    fn from(value: &ServerRegion) -> Self {
+        match value {
+            ServerRegion::BR1 => Self::BR,
+            ServerRegion::EUN1 => Self::EUNE,
+            ServerRegion::EUW1 => Self::EUW,
+            ServerRegion::JP1 => Self::JP,
+            ServerRegion::KR => Self::KR,
+            ServerRegion::LA1 => Self::LAN,
+            ServerRegion::LA2 => Self::LAS,
+            ServerRegion::ME1 => Self::ME,
+            ServerRegion::NA1 => Self::NA,
+            ServerRegion::OC1 => Self::OCE,
+            ServerRegion::PH2 => Self::PH,
+            ServerRegion::RU => Self::RU,
+            ServerRegion::SG2 => Self::SG,
+            ServerRegion::TH2 => Self::TH,
+            ServerRegion::TR1 => Self::TR,
+            ServerRegion::TW2 => Self::TW,
+            ServerRegion::VN2 => Self::VN,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PickType {
+    BlindPick,
+    DraftMode,
+    AllRandom,
+    TournamentDraft,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MapType {
+    SummonersRift,
+    HowlingAbyss,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpectatorType {
+    NONE,
+    LOBBYONLY,
+    ALL,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderRegistration {
+    pub region: TournamentRegion,
+    /// Callback URL game results are posted to, must use port 80 or 443
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentRegistration {
+    pub provider_id: i64,
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentCodeParameters {
+    /// PUUIDs allowed to join the lobby, anyone can join if None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_participants: Option<Vec<String>>,
+    /// Opaque string passed back in the game result callback
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+    pub team_size: u32,
+    pub pick_type: PickType,
+    pub map_type: MapType,
+    pub spectator_type: SpectatorType,
+    /// Whether the game can start without every allowed participant present
+    pub enough_players: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentCodeUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_participants: Option<Vec<String>>,
+    pub pick_type: PickType,
+    pub map_type: MapType,
+    pub spectator_type: SpectatorType,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentCode {
+    pub code: String,
+    pub spectators: SpectatorType,
+    pub lobby_name: String,
+    pub meta_data: Option<String>,
+    pub password: String,
+    pub team_size: u32,
+    pub provider_id: i64,
+    pub pick_type: PickType,
+    pub tournament_id: i64,
+    pub id: i64,
+    pub region: String,
+    pub map: MapType,
+    /// PUUIDs allowed to join the lobby
+    #[serde(default)]
+    pub participants: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyEventWrapper {
+    pub event_list: Vec<LobbyEvent>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyEvent {
+    /// Unix timestamp in milliseconds, sent as a string
+    pub timestamp: String,
+    /// e.g. "PracticeGameCreatedEvent" or "PlayerJoinedGameEvent"
+    pub event_type: String,
+    pub puuid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentGame {
+    pub winning_team: Vec<TournamentTeamMember>,
+    pub losing_team: Vec<TournamentTeamMember>,
+    pub short_code: String,
+    pub meta_data: Option<String>,
+    pub game_id: u64,
+    pub game_name: String,
+    pub game_type: String,
+    pub game_map: i64,
+    pub game_mode: String,
+    /// Platform ID, e.g. "NA1"
+    pub region: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentTeamMember {
+    pub puuid: String,
+}
+
+impl ProviderRegistration {
+    /// Registers a tournament provider, returning the provider ID.
+    pub async fn register(
+        &self,
+        region: &RoutingRegion,
+        api_key: &String,
+        api: TournamentApi,
+    ) -> Option<i64> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/{}/v5/providers?api_key={}",
+            region, api, api_key
+        );
+        let resp = reqwest::Client::new()
+            .post(request_url)
+            .json(self)
+            .send()
+            .await
+            .unwrap();
+        let resp = resp.text().await.expect("Could not parse");
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl TournamentRegistration {
+    /// Creates a tournament, returning the tournament ID.
+    pub async fn register(
+        &self,
+        region: &RoutingRegion,
+        api_key: &String,
+        api: TournamentApi,
+    ) -> Option<i64> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/{}/v5/tournaments?api_key={}",
+            region, api, api_key
+        );
+        let resp = reqwest::Client::new()
+            .post(request_url)
+            .json(self)
+            .send()
+            .await
+            .unwrap();
+        let resp = resp.text().await.expect("Could not parse");
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl TournamentCode {
+    /// Generates `count` codes (1 to 1000) for the tournament.
+    pub async fn create(
+        region: &RoutingRegion,
+        api_key: &String,
+        api: TournamentApi,
+        tournament_id: i64,
+        count: u32,
+        parameters: &TournamentCodeParameters,
+    ) -> Option<Vec<String>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/{}/v5/codes?tournamentId={}&count={}&api_key={}",
+            region, api, tournament_id, count, api_key
+        );
+        let resp = reqwest::Client::new()
+            .post(request_url)
+            .json(parameters)
+            .send()
+            .await
+            .unwrap();
+        let resp = resp.text().await.expect("Could not parse");
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    pub async fn by_code(
+        region: &RoutingRegion,
+        api_key: &String,
+        api: TournamentApi,
+        tournament_code: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/{}/v5/codes/{}?api_key={}",
+            region, api, tournament_code, api_key
+        );
+        let resp = cache::get(Endpoint::Tournament, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the code a match was played with, None if it was not a tournament game.
+    pub async fn by_match(
+        region: &RoutingRegion,
+        api_key: &String,
+        api: TournamentApi,
+        lol_match: &Match,
+    ) -> Option<Self> {
+        let code = lol_match.info.tournament_code.as_ref()?;
+        if code.is_empty() {
+            return None;
+        }
+        Self::by_code(region, api_key, api, code).await
+    }
+
+    /// Updates the lobby settings of a code, returns false on error.
+    ///
+    /// Not supported by the stub API.
+    pub async fn update(
+        region: &RoutingRegion,
+        api_key: &String,
+        tournament_code: &String,
+        update: &TournamentCodeUpdate,
+    ) -> bool {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/tournament/v5/codes/{}?api_key={}",
+            region, tournament_code, api_key
+        );
+        let resp = reqwest::Client::new()
+            .put(request_url)
+            .json(update)
+            .send()
+            .await
+            .unwrap();
+        if resp.status().is_success() {
+            return true;
+        }
+        let status = resp.status();
+        let resp = resp.text().await.expect("Could not parse");
+        error!("{status}, {}", resp);
+        false
+    }
+
+    pub async fn lobby_events(
+        region: &RoutingRegion,
+        api_key: &String,
+        api: TournamentApi,
+        tournament_code: &String,
+    ) -> Option<Vec<LobbyEvent>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/{}/v5/lobby-events/by-code/{}?api_key={}",
+            region, api, tournament_code, api_key
+        );
+        let resp = cache::get(Endpoint::Tournament, request_url).await;
+        match serde_json::from_str::<LobbyEventWrapper>(&resp) {
+            Ok(t) => Some(t.event_list),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the results of games played with the code.
+    ///
+    /// Not supported by the stub API.
+    pub async fn games(
+        region: &RoutingRegion,
+        api_key: &String,
+        tournament_code: &String,
+    ) -> Option<Vec<TournamentGame>> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/lol/tournament/v5/games/by-code/{}?api_key={}",
+            region, tournament_code, api_key
+        );
+        let resp = cache::get(Endpoint::Tournament, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl TournamentGame {
+    /// Match-v5 ID of the game, e.g. "NA1_1234567890".
+    pub fn match_id(&self) -> String {
+        Match::id_from_game(&self.region, self.game_id)
+    }
+
+    pub async fn full_match(&self, region: &RoutingRegion, api_key: &String) -> Option<Match> {
+        Match::by_match_id(region, api_key, &self.match_id()).await
+    }
+}
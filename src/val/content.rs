@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use crate::{
+    cache::{self, Endpoint},
+    val::shard::ValShard,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValContent {
+    pub version: String,
+    #[serde(default)]
+    pub characters: Vec<ContentItem>,
+    #[serde(default)]
+    pub maps: Vec<ContentItem>,
+    #[serde(default)]
+    pub chromas: Vec<ContentItem>,
+    #[serde(default)]
+    pub skins: Vec<ContentItem>,
+    #[serde(default)]
+    pub skin_levels: Vec<ContentItem>,
+    #[serde(default)]
+    pub equips: Vec<ContentItem>,
+    #[serde(default)]
+    pub game_modes: Vec<ContentItem>,
+    #[serde(default)]
+    pub sprays: Vec<ContentItem>,
+    #[serde(default)]
+    pub spray_levels: Vec<ContentItem>,
+    #[serde(default)]
+    pub charms: Vec<ContentItem>,
+    #[serde(default)]
+    pub charm_levels: Vec<ContentItem>,
+    #[serde(default)]
+    pub player_cards: Vec<ContentItem>,
+    #[serde(default)]
+    pub player_titles: Vec<ContentItem>,
+    #[serde(default)]
+    pub acts: Vec<Act>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentItem {
+    pub name: String,
+    /// Locale to name, only sent when no locale was requested
+    pub localized_names: Option<HashMap<String, String>>,
+    pub id: String,
+    pub asset_name: String,
+    pub asset_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Act {
+    pub name: String,
+    pub localized_names: Option<HashMap<String, String>>,
+    pub id: String,
+    pub is_active: bool,
+    /// ID of the episode the act belongs to
+    pub parent_id: Option<String>,
+    /// "act" or "episode"
+    #[serde(rename = "type")]
+    pub act_type: Option<String>,
+}
+
+impl ValContent {
+    /// Requests the content of the current patch, names are localized to `locale` if given.
+    pub async fn get(shard: &ValShard, api_key: &String, locale: Option<&str>) -> Option<Self> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/val/content/v1/contents?api_key={}",
+            shard, api_key
+        );
+        if let Some(locale) = locale {
+            request_url = format!("{}&locale={}", request_url, locale);
+        }
+        let resp = cache::get(Endpoint::ValContent, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Currently active act, excluding episodes.
+    pub fn active_act(&self) -> Option<&Act> {
+        self.acts
+            .iter()
+            .find(|act| act.is_active && act.act_type.as_deref() != Some("episode"))
+    }
+
+    /// Agent with the given character ID, IDs are compared case insensitively.
+    pub fn character(&self, character_id: &str) -> Option<&ContentItem> {
+        self.characters
+            .iter()
+            .find(|character| character.id.eq_ignore_ascii_case(character_id))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod content;
+pub mod ranked;
+pub mod shard;
+pub mod status;
+pub mod val_match;
@@ -0,0 +1,58 @@
+use crate::{
+    cache::{self, Endpoint},
+    val::shard::ValShard,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Leaderboard {
+    pub shard: String,
+    pub act_id: String,
+    pub total_players: i64,
+    pub players: Vec<LeaderboardPlayer>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardPlayer {
+    /// None for anonymous players
+    pub puuid: Option<String>,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub leaderboard_rank: i64,
+    pub ranked_rating: i64,
+    pub number_of_wins: i64,
+    pub competitive_tier: Option<i64>,
+}
+
+impl Leaderboard {
+    /// Requests a slice of an act's leaderboard, `size` is 1 to 200 and defaults to 200.
+    pub async fn by_act(
+        shard: &ValShard,
+        api_key: &String,
+        act_id: &String,
+        size: Option<u32>,
+        start_index: Option<u32>,
+    ) -> Option<Self> {
+        let mut request_url = format!(
+            "https://{}.api.riotgames.com/val/ranked/v1/leaderboards/by-act/{}?api_key={}",
+            shard, act_id, api_key
+        );
+        if let Some(size) = size {
+            request_url = format!("{}&size={}", request_url, size);
+        }
+        if let Some(start_index) = start_index {
+            request_url = format!("{}&startIndex={}", request_url, start_index);
+        }
+        let resp = cache::get(Endpoint::ValRanked, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
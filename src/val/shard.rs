@@ -0,0 +1,55 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::account::{ActiveShard, ShardedGame};
+
+/// VALORANT routes requests by shard rather than by platform or regional routing value.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValShard {
+    AP,
+    BR,
+    EU,
+    KR,
+    LATAM,
+    NA,
+    ESPORTS,
+}
+
+impl fmt::Display for ValShard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::AP => write!(f, "ap"),
+            Self::BR => write!(f, "br"),
+            Self::EU => write!(f, "eu"),
+            Self::KR => write!(f, "kr"),
+            Self::LATAM => write!(f, "latam"),
+            Self::NA => write!(f, "na"),
+            Self::ESPORTS => write!(f, "esports"),
+        }
+    }
+}
+
+impl ValShard {
+    /// Parses a shard name such as "eu", case insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "ap" => Some(Self::AP),
+            "br" => Some(Self::BR),
+            "eu" => Some(Self::EU),
+            "kr" => Some(Self::KR),
+            "latam" => Some(Self::LATAM),
+            "na" => Some(Self::NA),
+            "esports" => Some(Self::ESPORTS),
+            _ => None,
+        }
+    }
+
+    /// Shard of a VALORANT active-shard lookup, None for other games or unknown shards.
+    pub fn from_active_shard(shard: &ActiveShard) -> Option<Self> {
+        if shard.get_game() != ShardedGame::Valorant {
+            return None;
+        }
+        Self::from_name(shard.get_active_shard())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{
+    cache::{self, Endpoint},
+    status::PlatformData,
+    val::shard::ValShard,
+};
+use log::error;
+
+/// Requests the VALORANT status of a shard, which shares its format with lol-status-v4.
+pub async fn platform_data(shard: &ValShard, api_key: &String) -> Option<PlatformData> {
+    let request_url = format!(
+        "https://{}.api.riotgames.com/val/status/v1/platform-data?api_key={}",
+        shard, api_key
+    );
+    let resp = cache::get(Endpoint::Status, request_url).await;
+    match serde_json::from_str(&resp) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            error!("{e:?}, {}", resp);
+            None
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{
+    account::{RiotAccount, ShardedGame},
+    cache::{self, Endpoint},
+    region::routing::RoutingRegion,
+    val::shard::ValShard,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValMatch {
+    pub match_info: MatchInfo,
+    pub players: Vec<Player>,
+    #[serde(default)]
+    pub coaches: Vec<Coach>,
+    pub teams: Vec<Team>,
+    #[serde(default)]
+    pub round_results: Vec<RoundResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchInfo {
+    pub match_id: String,
+    pub map_id: String,
+    pub game_length_millis: Option<u64>,
+    pub game_start_millis: u64,
+    pub provisioning_flow_id: String,
+    pub is_completed: bool,
+    pub custom_game_name: String,
+    pub queue_id: String,
+    pub game_mode: String,
+    pub is_ranked: bool,
+    pub season_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Player {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub team_id: String,
+    pub party_id: String,
+    /// Agent ID, resolvable through `ValContent::character`
+    pub character_id: Option<String>,
+    pub stats: Option<PlayerStats>,
+    pub competitive_tier: i64,
+    pub player_card: String,
+    pub player_title: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    pub score: i64,
+    pub rounds_played: i64,
+    pub kills: i64,
+    pub deaths: i64,
+    pub assists: i64,
+    pub playtime_millis: i64,
+    pub ability_casts: Option<AbilityCasts>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AbilityCasts {
+    pub grenade_casts: Option<i64>,
+    pub ability1_casts: Option<i64>,
+    pub ability2_casts: Option<i64>,
+    pub ultimate_casts: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Coach {
+    pub puuid: String,
+    pub team_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    /// "Red" or "Blue" in standard games, the player's PUUID in deathmatch
+    pub team_id: String,
+    pub won: bool,
+    pub rounds_played: i64,
+    pub rounds_won: i64,
+    /// Number of kills in deathmatch
+    pub num_points: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundResult {
+    pub round_num: i64,
+    pub round_result: String,
+    pub round_ceremony: String,
+    pub winning_team: String,
+    pub bomb_planter: Option<String>,
+    pub bomb_defuser: Option<String>,
+    pub plant_round_time: Option<i64>,
+    pub plant_player_locations: Option<Vec<PlayerLocations>>,
+    pub plant_location: Option<Location>,
+    pub plant_site: Option<String>,
+    pub defuse_round_time: Option<i64>,
+    pub defuse_player_locations: Option<Vec<PlayerLocations>>,
+    pub defuse_location: Option<Location>,
+    #[serde(default)]
+    pub player_stats: Vec<PlayerRoundStats>,
+    pub round_result_code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerLocations {
+    pub puuid: String,
+    pub view_radians: f64,
+    pub location: Location,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerRoundStats {
+    pub puuid: String,
+    #[serde(default)]
+    pub kills: Vec<Kill>,
+    #[serde(default)]
+    pub damage: Vec<Damage>,
+    pub score: i64,
+    pub economy: Option<Economy>,
+    pub ability: Option<Ability>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Kill {
+    pub time_since_game_start_millis: i64,
+    pub time_since_round_start_millis: i64,
+    pub killer: String,
+    pub victim: String,
+    pub victim_location: Option<Location>,
+    #[serde(default)]
+    pub assistants: Vec<String>,
+    #[serde(default)]
+    pub player_locations: Vec<PlayerLocations>,
+    pub finishing_damage: Option<FinishingDamage>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FinishingDamage {
+    pub damage_type: String,
+    pub damage_item: String,
+    pub is_secondary_fire_mode: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Damage {
+    /// PUUID of the damaged player
+    pub receiver: String,
+    pub damage: i64,
+    pub legshots: i64,
+    pub bodyshots: i64,
+    pub headshots: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Economy {
+    pub loadout_value: i64,
+    pub weapon: String,
+    pub armor: String,
+    pub remaining: i64,
+    pub spent: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Ability {
+    pub grenade_effects: Option<String>,
+    pub ability1_effects: Option<String>,
+    pub ability2_effects: Option<String>,
+    pub ultimate_effects: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Matchlist {
+    pub puuid: String,
+    pub history: Vec<MatchlistEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchlistEntry {
+    pub match_id: String,
+    pub game_start_time_millis: u64,
+    pub queue_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentMatches {
+    /// Unix timestamp in milliseconds
+    pub current_time: u64,
+    /// Matches completed in the last 10 minutes
+    pub match_ids: Vec<String>,
+}
+
+impl ValMatch {
+    pub async fn by_match_id(
+        shard: &ValShard,
+        api_key: &String,
+        match_id: &String,
+    ) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/val/match/v1/matches/{}?api_key={}",
+            shard, match_id, api_key
+        );
+        let resp = cache::get(Endpoint::ValMatch, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Requests the IDs of matches completed in the last 10 minutes in the given queue,
+    /// e.g. "competitive" or "unrated".
+    pub async fn recent_by_queue(
+        shard: &ValShard,
+        api_key: &String,
+        queue: &String,
+    ) -> Option<RecentMatches> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/val/match/v1/recent-matches/by-queue/{}?api_key={}",
+            shard, queue, api_key
+        );
+        let resp = cache::get(Endpoint::ValMatchlist, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+}
+
+impl Matchlist {
+    pub async fn by_puuid(shard: &ValShard, api_key: &String, puuid: &String) -> Option<Self> {
+        let request_url = format!(
+            "https://{}.api.riotgames.com/val/match/v1/matchlists/by-puuid/{}?api_key={}",
+            shard, puuid, api_key
+        );
+        let resp = cache::get(Endpoint::ValMatchlist, request_url).await;
+        match serde_json::from_str(&resp) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                error!("{e:?}, {}", resp);
+                None
+            }
+        }
+    }
+
+    /// Looks up the account's active VALORANT shard and requests its matchlist there.
+    pub async fn by_account(
+        region: &RoutingRegion,
+        api_key: &String,
+        account: &RiotAccount,
+    ) -> Option<Self> {
+        let active_shard =
+            RiotAccount::active_shard(region, api_key, ShardedGame::Valorant, account.get_puuid())
+                .await?;
+        let Some(shard) = ValShard::from_active_shard(&active_shard) else {
+            error!(
+                "Unknown VALORANT shard '{}'",
+                active_shard.get_active_shard()
+            );
+            return None;
+        };
+        Self::by_puuid(&shard, api_key, account.get_puuid()).await
+    }
+}